@@ -23,6 +23,7 @@ class Settings(BaseSettings):
     frontend_path: str = "./frontend"
 
     # Speech Configuration
+    model_dir: str = "./models"  # Downloaded Whisper and Piper models live under here
     whisper_model_size: str = "base.en"  # Options: tiny.en, base.en, small.en, medium.en
     piper_model_path: str = "./models/piper/en_US-lessac-medium.onnx"
 
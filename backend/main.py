@@ -4,6 +4,7 @@ from fastapi.responses import FileResponse
 from fastapi.middleware.cors import CORSMiddleware
 from contextlib import asynccontextmanager
 from pathlib import Path
+from backend import __version__
 from backend.config import settings
 from backend.routers import chat, health, stt, tts
 from backend.services.ollama_service import ollama_service
@@ -42,7 +43,7 @@ async def lifespan(app: FastAPI):
 app = FastAPI(
     title="RenAI",
     description="Fast & Responsive AI Assistant for Home Network",
-    version="1.0.0",
+    version=__version__,
     lifespan=lifespan,
 )
 
@@ -83,7 +84,7 @@ else:
         """API root endpoint when frontend is not available."""
         return {
             "message": "RenAI API is running",
-            "version": "1.0.0",
+            "version": __version__,
             "docs": "/docs",
         }
 
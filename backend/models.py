@@ -1,5 +1,5 @@
 from pydantic import BaseModel, Field
-from typing import Optional
+from typing import Literal, Optional
 
 
 class ChatRequest(BaseModel):
@@ -22,8 +22,35 @@ class ModelInfo(BaseModel):
     name: str = Field(..., description="Model name")
 
 
+class VersionResponse(BaseModel):
+    """Response model for version endpoint."""
+
+    version: str = Field(..., description="Backend version")
+
+
 class HealthResponse(BaseModel):
     """Response model for health check endpoint."""
 
     status: str = Field(..., description="Health status")
     ollama: str = Field(..., description="Ollama connection status")
+
+
+class SaveResponse(BaseModel):
+    """Response model for save endpoint."""
+
+    status: str = Field(..., description="Save status")
+
+
+LogLevel = Literal["error", "warn", "info", "debug", "trace"]
+
+
+class LogLevelRequest(BaseModel):
+    """Request model for log level endpoint."""
+
+    level: LogLevel = Field(..., description="New log level, as named in the desktop app's settings")
+
+
+class LogLevelResponse(BaseModel):
+    """Response model for log level endpoint."""
+
+    level: LogLevel = Field(..., description="Log level now in effect")
@@ -1,9 +1,27 @@
+import logging
 from fastapi import APIRouter
-from backend.models import HealthResponse
+from uvicorn.logging import TRACE_LOG_LEVEL
+from backend import __version__
+from backend.models import (
+    HealthResponse,
+    LogLevelRequest,
+    LogLevelResponse,
+    SaveResponse,
+    VersionResponse,
+)
 from backend.services.ollama_service import ollama_service
 
 router = APIRouter()
 
+# Python logging levels for the level names the desktop app uses
+LOG_LEVELS = {
+    "error": logging.ERROR,
+    "warn": logging.WARNING,
+    "info": logging.INFO,
+    "debug": logging.DEBUG,
+    "trace": TRACE_LOG_LEVEL,
+}
+
 
 @router.get("/health", response_model=HealthResponse)
 async def health_check():
@@ -19,3 +37,46 @@ async def health_check():
         status="healthy" if ollama_status else "degraded",
         ollama="connected" if ollama_status else "disconnected",
     )
+
+
+@router.get("/version", response_model=VersionResponse)
+async def version():
+    """
+    Version endpoint.
+
+    Returns:
+        The running backend's version
+    """
+    return VersionResponse(version=__version__)
+
+
+@router.post("/save", response_model=SaveResponse)
+async def save():
+    """
+    Save endpoint, called by the desktop app just before it stops the backend.
+
+    Nothing is kept only in memory yet, so there is nothing to flush; the
+    response just confirms the backend is ready to be stopped.
+
+    Returns:
+        Save status
+    """
+    return SaveResponse(status="saved")
+
+
+@router.post("/log-level", response_model=LogLevelResponse)
+async def set_log_level(request: LogLevelRequest):
+    """
+    Log level endpoint, so the desktop app can change verbosity without a restart.
+
+    Args:
+        request: The new log level
+
+    Returns:
+        The log level now in effect
+    """
+    level = LOG_LEVELS[request.level]
+    for name in ("", "uvicorn", "uvicorn.error", "uvicorn.access"):
+        logging.getLogger(name).setLevel(level)
+
+    return LogLevelResponse(level=request.level)
@@ -8,6 +8,7 @@ import wave
 import json
 import os
 from pathlib import Path
+from backend.config import settings
 
 logger = logging.getLogger(__name__)
 
@@ -27,7 +28,7 @@ class PiperService:
 
     def _ensure_model_paths(self):
         """Ensure Piper model and config files exist"""
-        models_dir = Path("./models/piper")
+        models_dir = Path(settings.model_dir) / "piper"
 
         # Look for .onnx model file
         onnx_files = list(models_dir.glob("*.onnx"))
@@ -37,7 +38,7 @@ class PiperService:
             self.config_path = f"{self.model_path}.json"
             logger.info(f"Using Piper model: {self.model_path}")
         else:
-            logger.warning("No Piper model found in models/piper/")
+            logger.warning(f"No Piper model found in {models_dir}")
 
     async def synthesize(self, text: str) -> bytes:
         """
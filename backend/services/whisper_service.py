@@ -29,7 +29,7 @@ class WhisperService:
                     self._model_size,
                     device="cpu",
                     compute_type="int8",
-                    download_root="./models/whisper"
+                    download_root=os.path.join(settings.model_dir, "whisper")
                 )
                 logger.info("Whisper model loaded successfully")
             except Exception as e:
@@ -0,0 +1,48 @@
+/**
+ * Backend API location
+ *
+ * In the desktop app the backend may be running on a port other than 8000,
+ * or over HTTPS, so its real address is fetched from Tauri on boot and again
+ * whenever the backend comes back, since a restart may move it. Opened in a
+ * plain browser, the page is served by the backend itself and relative URLs
+ * are enough.
+ */
+let apiBase = '';
+
+// Settles once the latest lookup of the backend's address is done
+let apiBaseLookup = Promise.resolve();
+
+async function lookUpApiBase() {
+    try {
+        apiBase = await window.__TAURI__.core.invoke('get_backend_url');
+    } catch (error) {
+        console.error('Failed to get backend URL:', error);
+    }
+}
+
+/**
+ * Work out the API base URL and keep it current; call once before making any
+ * requests
+ */
+async function initApiBase() {
+    if (!window.__TAURI__) {
+        return;
+    }
+
+    const { listen } = window.__TAURI__.event;
+    const refresh = () => {
+        apiBaseLookup = lookUpApiBase();
+    };
+    await listen('backend-state', refresh);
+    await listen('backend-ready', refresh);
+
+    refresh();
+    await apiBaseLookup;
+}
+
+/**
+ * Full URL for a backend path such as /api/models
+ */
+function apiUrl(path) {
+    return apiBase + path;
+}
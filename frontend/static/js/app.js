@@ -27,7 +27,7 @@ autoTtsToggle.checked = settings.autoTts;
  */
 async function loadModels() {
     try {
-        const response = await fetch('/api/models');
+        const response = await fetch(apiUrl('/api/models'));
 
         if (!response.ok) {
             throw new Error('Failed to fetch models');
@@ -246,15 +246,31 @@ chatManager.addMessage = function(content, role) {
  * Initialize app
  */
 async function init() {
-    // Load available models
-    await loadModels();
-
     // Focus input
     messageInput.focus();
 
+    // Registered first, so the address is being looked up again by the time
+    // onBackendReady hears that the backend is back
+    await initApiBase();
+
+    // Nothing is requested until the backend serves, and the models are
+    // reloaded whenever it comes back
+    await watchBackend(onBackendReady);
+}
+
+/**
+ * Talk to the backend once it is serving
+ */
+async function onBackendReady() {
+    // A restart may have moved it, so wait for api.js to find it again
+    await apiBaseLookup;
+
+    // Load available models
+    await loadModels();
+
     // Check backend health
     try {
-        const response = await fetch('/health');
+        const response = await fetch(apiUrl('/health'));
         const health = await response.json();
 
         if (health.ollama !== 'connected') {
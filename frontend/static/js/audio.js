@@ -95,7 +95,7 @@ class AudioManager {
         formData.append('audio', audioBlob, 'recording.webm');
 
         try {
-            const response = await fetch('/api/stt/transcribe', {
+            const response = await fetch(apiUrl('/api/stt/transcribe'), {
                 method: 'POST',
                 body: formData
             });
@@ -126,7 +126,7 @@ class AudioManager {
             // Stop any currently playing audio
             this.stopPlayback();
 
-            const response = await fetch('/api/tts/synthesize', {
+            const response = await fetch(apiUrl('/api/tts/synthesize'), {
                 method: 'POST',
                 headers: {
                     'Content-Type': 'application/json'
@@ -0,0 +1,104 @@
+/**
+ * Backend readiness
+ *
+ * In the desktop app the page loads while the backend may still be starting,
+ * so it waits behind an overlay until the backend serves instead of failing
+ * its first requests. The overlay comes back whenever the backend goes away,
+ * with a button to start it again.
+ */
+const backendOverlay = document.getElementById('backend-overlay');
+const backendOverlayStatus = document.getElementById('backend-overlay-status');
+const backendOverlayError = document.getElementById('backend-overlay-error');
+const backendOverlayButton = document.getElementById('backend-overlay-button');
+
+// Overlay button for the states that offer one, and the command it invokes
+const BACKEND_ACTIONS = {
+    crashed: { label: 'Retry', command: 'start_backend' },
+    stopped: { label: 'Start Backend', command: 'start_backend' },
+    paused: { label: 'Resume Backend', command: 'resume_backend' },
+    suspended: { label: 'Resume Backend', command: 'resume_backend' }
+};
+
+function isServing(state) {
+    return state.state === 'running' || state.state === 'external';
+}
+
+function backendMessage(state) {
+    switch (state.state) {
+        case 'crashed':
+            return state.error ? 'The backend failed to start' : 'The backend stopped unexpectedly';
+        case 'stopped':
+            return 'The backend is stopped';
+        case 'paused':
+        case 'suspended':
+            return 'The backend is paused';
+        default:
+            return 'Starting backend…';
+    }
+}
+
+/**
+ * Cover the page while the backend isn't serving, saying why
+ */
+function showBackendState(state) {
+    if (isServing(state)) {
+        backendOverlay.style.display = 'none';
+        return;
+    }
+
+    backendOverlay.style.display = 'flex';
+    backendOverlayStatus.textContent = backendMessage(state);
+    backendOverlayError.textContent = state.error || '';
+    backendOverlayError.style.display = state.error ? 'block' : 'none';
+
+    const action = BACKEND_ACTIONS[state.state];
+    backendOverlayButton.style.display = action ? 'inline-block' : 'none';
+    if (action) {
+        backendOverlayButton.textContent = action.label;
+        backendOverlayButton.dataset.command = action.command;
+    }
+}
+
+backendOverlayButton.addEventListener('click', () => {
+    const command = backendOverlayButton.dataset.command;
+    showBackendState({ state: 'starting' });
+
+    // How it went arrives as a backend-state event
+    window.__TAURI__.core.invoke(command).catch((error) => {
+        console.error(`Failed to run ${command}:`, error);
+    });
+});
+
+/**
+ * Call onReady each time the backend starts serving, the first time included.
+ * Opened in a plain browser the page is served by the backend itself, so it
+ * is ready already.
+ */
+async function watchBackend(onReady) {
+    if (!window.__TAURI__) {
+        await onReady();
+        return;
+    }
+
+    const { invoke } = window.__TAURI__.core;
+    const { listen } = window.__TAURI__.event;
+
+    let serving = false;
+    const update = async (state) => {
+        showBackendState(state);
+
+        const wasServing = serving;
+        serving = isServing(state);
+        if (serving && !wasServing) {
+            await onReady();
+        }
+    };
+
+    // Listen first, so a change between the two isn't missed
+    await listen('backend-state', (event) => update(event.payload));
+    try {
+        await update(await invoke('get_backend_state'));
+    } catch (error) {
+        console.error('Failed to get backend state:', error);
+    }
+}
@@ -1,3 +1,12 @@
+/**
+ * Tell the desktop app whether a response is being generated, so quitting can ask first
+ */
+function setTaskInProgress(inProgress) {
+    if (window.__TAURI__) {
+        window.__TAURI__.core.invoke('set_task_in_progress', { inProgress }).catch(() => {});
+    }
+}
+
 class ChatManager {
     constructor() {
         this.messagesContainer = document.getElementById('messages');
@@ -33,9 +42,11 @@ class ChatManager {
 
         console.log('Sending request to /api/chat/stream:', requestBody);
 
+        setTaskInProgress(true);
+
         try {
             // Create EventSource for streaming
-            const response = await fetch('/api/chat/stream', {
+            const response = await fetch(apiUrl('/api/chat/stream'), {
                 method: 'POST',
                 headers: {
                     'Content-Type': 'application/json',
@@ -103,6 +114,8 @@ class ChatManager {
                 this.currentAssistantMessage.remove();
                 this.currentAssistantMessage = null;
             }
+        } finally {
+            setTaskInProgress(false);
         }
     }
 
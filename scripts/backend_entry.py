@@ -0,0 +1,40 @@
+#!/usr/bin/env python3
+"""
+Entry point frozen into the bundled renai-backend executable.
+
+Accepts the same options the desktop app passes to `python -m uvicorn`.
+"""
+
+import argparse
+import multiprocessing
+
+import uvicorn
+
+
+def main():
+    parser = argparse.ArgumentParser(prog="renai-backend")
+    parser.add_argument("--host", default="127.0.0.1")
+    parser.add_argument("--port", type=int, default=8000)
+    parser.add_argument("--workers", type=int, default=1)
+    parser.add_argument("--reload", action="store_true")
+    parser.add_argument("--log-level", default="info")
+    parser.add_argument("--ssl-certfile")
+    parser.add_argument("--ssl-keyfile")
+    args = parser.parse_args()
+
+    uvicorn.run(
+        "backend.main:app",
+        host=args.host,
+        port=args.port,
+        workers=args.workers,
+        reload=args.reload,
+        log_level=args.log_level,
+        ssl_certfile=args.ssl_certfile,
+        ssl_keyfile=args.ssl_keyfile,
+    )
+
+
+if __name__ == "__main__":
+    # Needed for --workers in a frozen executable
+    multiprocessing.freeze_support()
+    main()
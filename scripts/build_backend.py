@@ -0,0 +1,56 @@
+#!/usr/bin/env python3
+"""
+Build the standalone renai-backend executable for release bundles
+
+Output goes to src-tauri/binaries/, which tauri.release.conf.json adds to the
+bundle resources:
+
+    pip install pyinstaller
+    python scripts/build_backend.py
+    cargo tauri build --config src-tauri/tauri.release.conf.json
+"""
+
+import subprocess
+import sys
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parent.parent
+DIST_DIR = ROOT / "src-tauri" / "binaries"
+BUILD_DIR = ROOT / "build" / "pyinstaller"
+
+
+def main():
+    command = [
+        sys.executable,
+        "-m",
+        "PyInstaller",
+        "--onefile",
+        "--noconfirm",
+        "--name",
+        "renai-backend",
+        "--distpath",
+        str(DIST_DIR),
+        "--workpath",
+        str(BUILD_DIR),
+        "--specpath",
+        str(BUILD_DIR),
+        "--paths",
+        str(ROOT),
+        # Loaded by import string, so PyInstaller can't find them on its own
+        "--collect-submodules",
+        "backend",
+        "--collect-submodules",
+        "uvicorn",
+        str(ROOT / "scripts" / "backend_entry.py"),
+    ]
+
+    print("Building renai-backend...")
+    result = subprocess.run(command, cwd=ROOT)
+    if result.returncode != 0:
+        sys.exit(result.returncode)
+
+    print(f"✓ Backend executable written to {DIST_DIR}")
+
+
+if __name__ == "__main__":
+    main()
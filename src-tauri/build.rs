@@ -1,3 +1,35 @@
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[path = "src/civil.rs"]
+mod civil;
+
 fn main() {
+  // Shown in the About dialog
+  println!("cargo:rustc-env=RENAI_GIT_COMMIT={}", git_commit());
+  println!("cargo:rustc-env=RENAI_BUILD_DATE={}", build_date());
+  println!("cargo:rerun-if-changed=../.git/HEAD");
+
   tauri_build::build()
 }
+
+fn git_commit() -> String {
+  Command::new("git")
+    .args(["rev-parse", "--short", "HEAD"])
+    .output()
+    .ok()
+    .filter(|output| output.status.success())
+    .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    .unwrap_or_else(|| "unknown".to_string())
+}
+
+// Today's UTC date as YYYY-MM-DD
+fn build_date() -> String {
+  let secs = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|elapsed| elapsed.as_secs())
+    .unwrap_or(0);
+
+  let (year, month, day) = civil::date_from_unix(secs);
+  format!("{:04}-{:02}-{:02}", year, month, day)
+}
@@ -0,0 +1,32 @@
+use tauri::menu::{Menu, MenuItem, MenuItemKind};
+use tauri::{AppHandle, Runtime};
+
+use crate::i18n::{self, Label};
+
+const QUIT_ID: &str = "app_quit";
+
+// Use Tauri's default menu bar, except that its Quit, and with it Cmd+Q, quits
+// the way the tray's Quit does rather than through AppKit's terminate: asking
+// first while a response is being generated and taking the tray icon down.
+pub fn install<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
+    let menu = Menu::default(app)?;
+
+    // The app menu comes first, with the predefined Quit as its last item
+    if let Some(MenuItemKind::Submenu(app_menu)) = menu.items()?.into_iter().next() {
+        let items = app_menu.items()?;
+        if let Some(MenuItemKind::Predefined(_)) = items.last() {
+            app_menu.remove_at(items.len() - 1)?;
+            let label = i18n::text(i18n::language(app), Label::Quit);
+            let quit = MenuItem::with_id(app, QUIT_ID, label, true, Some("Cmd+Q"))?;
+            app_menu.append(&quit)?;
+        }
+    }
+    app.set_menu(menu)?;
+
+    app.on_menu_event(|app, event| {
+        if event.id() == QUIT_ID {
+            crate::tray::request_quit(app);
+        }
+    });
+    Ok(())
+}
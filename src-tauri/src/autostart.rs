@@ -0,0 +1,53 @@
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_autostart::ManagerExt;
+
+use crate::settings;
+
+// Whether RenAI is actually registered to start on login. The user can change
+// this outside the app, so the OS entry wins over the saved setting.
+pub fn is_enabled<R: Runtime>(app_handle: &AppHandle<R>) -> bool {
+    app_handle.autolaunch().is_enabled().unwrap_or_else(|e| {
+        eprintln!("Warning: Failed to read start-on-login status: {}", e);
+        false
+    })
+}
+
+// Bring the saved setting in line with the registered status on startup
+pub fn sync_setting<R: Runtime>(app_handle: &AppHandle<R>) -> bool {
+    let enabled = is_enabled(app_handle);
+
+    let mut settings = settings::load(app_handle);
+    if settings.autostart != enabled {
+        settings.autostart = enabled;
+        if let Err(e) = settings::save(app_handle, &settings) {
+            eprintln!("Warning: {}", e);
+        }
+    }
+
+    enabled
+}
+
+// Register or remove the login item (registry on Windows, a LaunchAgent on
+// macOS, an autostart .desktop entry on Linux) and remember the choice
+pub fn set_enabled<R: Runtime>(app_handle: &AppHandle<R>, enabled: bool) -> Result<(), String> {
+    apply(app_handle, enabled)?;
+
+    let mut settings = settings::load(app_handle);
+    settings.autostart = enabled;
+    settings::save(app_handle, &settings)
+}
+
+// Only touch the OS entry, for callers that save the settings themselves
+pub fn apply<R: Runtime>(app_handle: &AppHandle<R>, enabled: bool) -> Result<(), String> {
+    if is_enabled(app_handle) == enabled {
+        return Ok(());
+    }
+
+    let autolaunch = app_handle.autolaunch();
+    let result = if enabled {
+        autolaunch.enable()
+    } else {
+        autolaunch.disable()
+    };
+    result.map_err(|e| format!("Failed to update start-on-login: {}", e))
+}
@@ -1,80 +1,2393 @@
-use std::process::{Child, Command};
-use std::sync::Mutex;
-use tauri::Manager;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::net::{IpAddr, TcpListener};
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{path::BaseDirectory, AppHandle, Emitter, Manager, Runtime, State};
 
-static BACKEND_PROCESS: Mutex<Option<Child>> = Mutex::new(None);
+use crate::settings::LogLevel;
 
-pub fn start_backend_server(app_handle: &tauri::AppHandle) -> Result<(), String> {
-    println!("Starting FastAPI backend server...");
+const DEFAULT_HOST: &str = "127.0.0.1";
+const DEFAULT_PORT: u16 = 8000;
+const DEFAULT_SHUTDOWN_GRACE_SECS: u64 = 5;
+const DEFAULT_NETWORK_WAIT_SECS: u64 = 10;
+const DEFAULT_MIN_PYTHON: &str = "3.9";
 
-    // Find Python executable
-    let python_cmd = if cfg!(target_os = "windows") {
-        "python"
+// PyInstaller build of the backend shipped in the resource dir of release builds
+const BUNDLED_BACKEND: &str = if cfg!(windows) {
+    "binaries/renai-backend.exe"
+} else {
+    "binaries/renai-backend"
+};
+
+// How many ports above the configured one to try when it is taken
+const PORT_SCAN_RANGE: u16 = 20;
+
+// Override the configured host and port, e.g. for running several instances in CI
+const HOST_ENV: &str = "RENAI_BACKEND_HOST";
+const PORT_ENV: &str = "RENAI_BACKEND_PORT";
+
+// Records the pid and port of the backend we spawned, in the app data dir, so
+// one orphaned by a crash can be found and stopped on the next launch
+const PID_FILE: &str = "backend.pid";
+
+// Tells the backend where to keep models and other per-user data
+const APP_DATA_DIR_ENV: &str = "RENAI_APP_DATA_DIR";
+
+// Env vars whose names contain one of these have their values hidden in logs
+const SECRET_ENV_MARKERS: &[&str] = &["KEY", "TOKEN", "SECRET", "PASSWORD", "CREDENTIAL"];
+
+const READY_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const NETWORK_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// Longest backend_request waits for a response, which may be a whole LLM reply
+const PROXY_TIMEOUT: Duration = Duration::from_secs(120);
+
+// How long a quitting app waits for the backend to confirm it saved its state
+const SAVE_TIMEOUT: Duration = Duration::from_secs(3);
+
+// Launch attempts before giving up, doubling the delay between them
+const START_ATTEMPTS: u32 = 3;
+const START_BACKOFF: Duration = Duration::from_millis(500);
+
+// The watchdog gives up after this many automatic restarts to avoid crash loops
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(3);
+const MAX_AUTO_RESTARTS: u32 = 3;
+
+// A backend that stays up this long has recovered, and its earlier crashes
+// no longer count toward MAX_AUTO_RESTARTS
+const STABLE_UPTIME: Duration = Duration::from_secs(60);
+
+// Lines of backend.log sent with backend-crashed, usually enough for the traceback
+const CRASH_LOG_LINES: usize = 20;
+
+// Lifecycle events emitted to the frontend
+const BACKEND_STARTING: &str = "backend-starting";
+const BACKEND_READY_EVENT: &str = "backend-ready";
+const BACKEND_CRASHED: &str = "backend-crashed";
+const BACKEND_STOPPED: &str = "backend-stopped";
+const BACKEND_PAUSED: &str = "backend-paused";
+const BACKEND_RESTARTING: &str = "backend-restarting";
+// The backend is reachable from other machines
+const BACKEND_EXPOSED: &str = "backend-exposed";
+// Sent after each of the above with the resulting BackendState
+const BACKEND_STATE_EVENT: &str = "backend-state";
+
+// Owns the backend process and everything known about it. One instance is
+// managed by Tauri so that commands, the watchdog and the tray all read the
+// same state; tests can make their own and hand it a stand-in process.
+#[derive(Default)]
+pub struct BackendManager {
+    process: Mutex<Option<BackendGuard>>,
+
+    // Config the current backend process was started with
+    config: Mutex<Option<BackendConfig>>,
+
+    // Config the first-run wizard is trying out, started instead of the
+    // settings until the wizard saves it
+    trial_config: Mutex<Option<BackendConfig>>,
+
+    // When the current backend was spawned; the startup timeout counts from here
+    spawned_at: Mutex<Option<Instant>>,
+
+    // Set once the readiness probe succeeds, cleared whenever the process is replaced
+    ready: AtomicBool,
+
+    // Set when we are using a backend someone else started, which we must not kill
+    externally_managed: AtomicBool,
+
+    // Set when a start or restart gave up, so the status reads Crashed rather than Stopped
+    start_failed: AtomicBool,
+
+    // Why the last start or restart gave up, for the frontend's waiting screen
+    start_error: Mutex<Option<String>>,
+
+    // Set while the user has paused the backend; it stays stopped until resumed
+    paused: AtomicBool,
+
+    // Set while a paused backend is suspended rather than stopped
+    suspended: AtomicBool,
+
+    // Automatic restarts the watchdog has made after crashes, since the backend
+    // last stayed up for STABLE_UPTIME
+    restarts: AtomicU32,
+
+    // How the last backend that exited on its own went, for diagnostics
+    last_exit: Mutex<Option<ExitStatus>>,
+
+    // Every restart, automatic or asked for, and when the last one happened
+    // (milliseconds since the Unix epoch)
+    restart_count: AtomicU32,
+    last_restart: Mutex<Option<u64>>,
+
+    // Held for a whole stop/start/ready cycle so overlapping restarts can't end up
+    // with two backends fighting over the port
+    lifecycle: Mutex<()>,
+}
+
+impl BackendManager {
+    // Config of the most recently started backend, or the defaults if none was started
+    pub fn active_config(&self) -> BackendConfig {
+        self.config.lock().unwrap().clone().unwrap_or_default()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.process
+            .lock()
+            .unwrap()
+            .as_mut()
+            .map(|p| p.try_wait().ok().flatten().is_none())
+            .unwrap_or(false)
+    }
+
+    // How the current process exited, or None while it runs or if there is none
+    fn exit_status(&self) -> Option<ExitStatus> {
+        self.process
+            .lock()
+            .unwrap()
+            .as_mut()
+            .and_then(|p| p.try_wait().ok().flatten())
+    }
+
+    // How long the current process has been running; reset by every start
+    pub fn uptime(&self) -> Option<Duration> {
+        if !self.is_running() {
+            return None;
+        }
+        self.spawned_at.lock().unwrap().map(|spawned| spawned.elapsed())
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    pub fn restarts(&self) -> u32 {
+        self.restarts.load(Ordering::SeqCst)
+    }
+
+    pub fn last_exit(&self) -> Option<ExitStatus> {
+        *self.last_exit.lock().unwrap()
+    }
+
+    pub fn restart_count(&self) -> u32 {
+        self.restart_count.load(Ordering::SeqCst)
+    }
+
+    pub fn last_restart(&self) -> Option<u64> {
+        *self.last_restart.lock().unwrap()
+    }
+
+    pub fn state(&self) -> BackendState {
+        let status = self.status();
+        let error = match status {
+            BackendStatus::Crashed => self.start_error.lock().unwrap().clone(),
+            _ => None,
+        };
+        BackendState { status, error }
+    }
+
+    // Count a restart and return the new total
+    fn record_restart(&self) -> u32 {
+        *self.last_restart.lock().unwrap() = Some(now_millis());
+        self.restart_count.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    pub fn status(&self) -> BackendStatus {
+        let mut guard = self.process.lock().unwrap();
+
+        match guard.as_mut() {
+            None if self.externally_managed.load(Ordering::SeqCst) => BackendStatus::External {
+                port: self.active_config().port,
+            },
+            None if self.is_paused() => BackendStatus::Paused,
+            None if self.start_failed.load(Ordering::SeqCst) => BackendStatus::Crashed,
+            None => BackendStatus::Stopped,
+            // The handle is only taken by stop, so an exited
+            // process that is still stored here died on its own. Exiting with 0
+            // was its own decision, so only other codes count as a crash.
+            Some(process) => match process.try_wait() {
+                Ok(None) if self.suspended.load(Ordering::SeqCst) => BackendStatus::Suspended {
+                    pid: process.id(),
+                    port: self.active_config().port,
+                },
+                Ok(None) if self.ready.load(Ordering::SeqCst) => BackendStatus::Running {
+                    pid: process.id(),
+                    port: self.active_config().port,
+                },
+                Ok(None) => BackendStatus::Starting,
+                Ok(Some(exit)) if exit.success() => BackendStatus::Stopped,
+                _ => BackendStatus::Crashed,
+            },
+        }
+    }
+}
+
+fn manager<R: Runtime>(app_handle: &AppHandle<R>) -> State<'_, BackendManager> {
+    app_handle.state::<BackendManager>()
+}
+
+#[derive(Debug)]
+pub enum BackendError {
+    // No Python interpreter to run the backend with
+    PythonNotFound,
+    // The interpreter is older than the backend's min_python, e.g. "3.6.9"
+    PythonTooOld { found: String, required: String },
+    // The tool the configured launcher runs through, e.g. conda, isn't installed
+    LauncherNotFound(&'static str),
+    // Something else is already listening on the port
+    PortInUse(u16),
+    SpawnFailed(io::Error),
+    // Spawned, but never answered its health check
+    StartupTimeout(Duration),
+    // Exited before answering its health check
+    ExitedEarly,
+    // A backend we manage is already running
+    AlreadyRunning,
+    // The settings or environment can't be used to start the backend
+    InvalidConfig(String),
+    // Every launch attempt failed; one error per attempt, in order
+    AttemptsExhausted(Vec<BackendError>),
+}
+
+impl BackendError {
+    // Whether trying again could help, as opposed to needing the user to fix something
+    fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            BackendError::PortInUse(_)
+                | BackendError::SpawnFailed(_)
+                | BackendError::StartupTimeout(_)
+                | BackendError::ExitedEarly
+        )
+    }
+
+    // The error behind a failed retry loop, or this error itself
+    pub fn root_cause(&self) -> &BackendError {
+        match self {
+            BackendError::AttemptsExhausted(attempts) => {
+                attempts.last().map(|e| e.root_cause()).unwrap_or(self)
+            }
+            _ => self,
+        }
+    }
+}
+
+impl fmt::Display for BackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackendError::PythonNotFound => write!(f, "No Python interpreter was found"),
+            BackendError::PythonTooOld { found, required } => write!(
+                f,
+                "Python {} is too old to run the backend, which needs Python {} or newer",
+                found, required
+            ),
+            BackendError::LauncherNotFound(tool) => {
+                write!(f, "The backend is set to run through {}, but {} was not found", tool, tool)
+            }
+            BackendError::PortInUse(port) => write!(f, "Port {} is already in use", port),
+            BackendError::SpawnFailed(e) => write!(f, "Failed to start backend: {}", e),
+            BackendError::StartupTimeout(timeout) => {
+                write!(f, "Backend did not become ready within {:?} of starting", timeout)
+            }
+            BackendError::ExitedEarly => write!(f, "Backend exited before becoming ready"),
+            BackendError::AlreadyRunning => write!(f, "Backend is already running"),
+            BackendError::InvalidConfig(message) => write!(f, "{}", message),
+            BackendError::AttemptsExhausted(attempts) => {
+                write!(f, "Backend failed to start after {} attempts (", attempts.len())?;
+                for (index, e) in attempts.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "attempt {}: {}", index + 1, e)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BackendError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BackendError::SpawnFailed(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// Owns the backend child. Dropping it kills the process tree if it is still
+// running and reaps it, so replacing or discarding a handle never leaves a zombie.
+pub struct BackendGuard(Child);
+
+impl BackendGuard {
+    pub fn new(child: Child) -> Self {
+        Self(child)
+    }
+}
+
+impl Deref for BackendGuard {
+    type Target = Child;
+
+    fn deref(&self) -> &Child {
+        &self.0
+    }
+}
+
+impl DerefMut for BackendGuard {
+    fn deref_mut(&mut self) -> &mut Child {
+        &mut self.0
+    }
+}
+
+impl Drop for BackendGuard {
+    fn drop(&mut self) {
+        if let Ok(None) = self.0.try_wait() {
+            if !kill_tree(&self.0) {
+                let _ = self.0.kill();
+            }
+        }
+        let _ = self.0.wait();
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum BackendStatus {
+    Starting,
+    Running { pid: u32, port: u16 },
+    // A backend we didn't spawn was already serving on the configured port
+    External { port: u16 },
+    Stopped,
+    // Stopped by the user through pause, waiting to be resumed
+    Paused,
+    // Paused with SIGSTOP, so it is still in memory and resumes where it was
+    Suspended { pid: u32, port: u16 },
+    Crashed,
+}
+
+// The status along with what went wrong, so a page can hold off its requests
+// until the backend serves and explain a failed start
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendState {
+    #[serde(flatten)]
+    status: BackendStatus,
+    // Only set while the status is Crashed after a start gave up
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct LifecyclePayload {
+    port: u16,
+    // Milliseconds since the Unix epoch
+    timestamp: u64,
+    #[serde(flatten)]
+    crash: Option<CrashDetails>,
+}
+
+// Sent along with backend-crashed so the frontend can show why
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CrashDetails {
+    // None if it was killed by a signal
+    exit_code: Option<i32>,
+    log_tail: Option<String>,
+}
+
+// A backend reply forwarded to the frontend by backend_request
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendResponse {
+    status: u16,
+    body: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ExposedPayload {
+    host: String,
+    port: u16,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RestartPayload {
+    restarts: u32,
+    max_restarts: u32,
+    // All restarts so far, including ones the user asked for
+    restart_count: u32,
+    last_restart: Option<u64>,
+}
+
+// Scheduling priority of the backend relative to the rest of the system
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessPriority {
+    // Keeps the machine responsive during heavy generation
+    Low,
+    #[default]
+    Normal,
+    // Raising priority needs root on Unix, so for anyone else it does nothing there
+    High,
+}
+
+// How uvicorn is started when the bundled backend isn't used
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BackendLauncher {
+    // `python -m uvicorn` with pythonPath or the platform default python
+    #[default]
+    Module,
+    // `python -m uvicorn` with this interpreter
+    Python(PathBuf),
+    // `conda run -n <env> python -m uvicorn`
+    CondaRun(String),
+    // `poetry run uvicorn`, using the project's poetry environment
+    PoetryRun,
+}
+
+impl BackendLauncher {
+    // Fail early, naming the tool, if the launcher can't be run
+    fn check_available(&self) -> Result<(), BackendError> {
+        match self {
+            BackendLauncher::Module => Ok(()),
+            BackendLauncher::Python(path) => validate_interpreter(path),
+            BackendLauncher::CondaRun(env) => {
+                if env.trim().is_empty() {
+                    return Err(BackendError::InvalidConfig(
+                        "The conda launcher needs an environment name".to_string(),
+                    ));
+                }
+                conda_executable()
+                    .map(|_| ())
+                    .ok_or(BackendError::LauncherNotFound("conda"))
+            }
+            BackendLauncher::PoetryRun => find_on_path("poetry")
+                .map(|_| ())
+                .ok_or(BackendError::LauncherNotFound("poetry")),
+        }
+    }
+
+    fn command(&self, config: &BackendConfig) -> Command {
+        let mut command = self.python(config);
+        command.args(["-m", "uvicorn", "backend.main:app"]);
+        command
+    }
+
+    // Runs the Python this launcher starts the backend with; the caller adds
+    // the arguments
+    fn python(&self, config: &BackendConfig) -> Command {
+        match self {
+            BackendLauncher::Module => Command::new(python_command(config)),
+            BackendLauncher::Python(path) => Command::new(path),
+            BackendLauncher::CondaRun(env) => {
+                let conda = conda_executable().unwrap_or_else(|| PathBuf::from("conda"));
+                let mut command = Command::new(conda);
+                // Without --no-capture-output conda holds the output until exit
+                command.args(["run", "--no-capture-output", "-n", env, "python"]);
+                command
+            }
+            BackendLauncher::PoetryRun => {
+                let poetry = find_on_path("poetry").unwrap_or_else(|| PathBuf::from("poetry"));
+                let mut command = Command::new(poetry);
+                command.args(["run", "python"]);
+                command
+            }
+        }
+    }
+
+    // What a spawn failing with NotFound means for this launcher
+    fn not_found(&self) -> BackendError {
+        match self {
+            BackendLauncher::Module | BackendLauncher::Python(_) => BackendError::PythonNotFound,
+            BackendLauncher::CondaRun(_) => BackendError::LauncherNotFound("conda"),
+            BackendLauncher::PoetryRun => BackendError::LauncherNotFound("poetry"),
+        }
+    }
+}
+
+// conda points CONDA_EXE at itself in activated shells; otherwise look on PATH
+fn conda_executable() -> Option<PathBuf> {
+    std::env::var_os("CONDA_EXE")
+        .map(PathBuf::from)
+        .filter(|path| path.is_file())
+        .or_else(|| find_on_path("conda"))
+}
+
+// Full path of a tool on PATH. Windows needs the extension spelled out, since
+// conda and poetry are often .bat or .cmd shims there.
+fn find_on_path(tool: &str) -> Option<PathBuf> {
+    let names: Vec<String> = if cfg!(windows) {
+        ["exe", "bat", "cmd"]
+            .iter()
+            .map(|ext| format!("{}.{}", tool, ext))
+            .collect()
     } else {
-        "python3"
+        vec![tool.to_string()]
+    };
+
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+        .find(|candidate| candidate.is_file())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BackendConfig {
+    pub host: String,
+    pub port: u16,
+    // Must be set to serve on a non-loopback host; the API has no authentication,
+    // so by default it stays on 127.0.0.1
+    pub allow_network: bool,
+    // How long uvicorn gets to shut down cleanly before it is force-killed.
+    // Windows can only kill the windowless backend, so there it just bounds
+    // the wait for an orphan's port to be released.
+    #[serde(alias = "shutdown_grace_secs")]
+    pub shutdown_grace_secs: u64,
+    // Interpreter to run the backend with, e.g. a project .venv; the platform
+    // default python is used when unset
+    #[serde(alias = "python_path")]
+    pub python_path: Option<PathBuf>,
+    // Runs the backend through conda or poetry instead of plain python
+    pub launcher: BackendLauncher,
+    // Extra environment variables for the backend, e.g. API keys or model paths
+    pub env: HashMap<String, String>,
+    // .env file of KEY=VALUE lines to add to the environment, relative to the
+    // backend directory unless absolute; variables in `env` take precedence
+    pub env_file: Option<PathBuf>,
+    // uvicorn worker processes; more than one helps throughput on multi-core machines
+    pub workers: usize,
+    // Restart the backend when its source changes, for development
+    pub reload: bool,
+    pub process_priority: ProcessPriority,
+    // Certificate and private key to serve HTTPS with; both or neither must be set
+    pub tls_cert: Option<PathBuf>,
+    pub tls_key: Option<PathBuf>,
+    // Route probed for readiness and health, for backends that don't serve /health
+    pub health_path: String,
+    // How long a start waits for the host's interface to come up, e.g. just
+    // after waking from sleep; 0 skips the check
+    pub network_wait_secs: u64,
+    // Oldest Python, as MAJOR.MINOR, that a Python start accepts
+    pub min_python: String,
+}
+
+impl Default for BackendConfig {
+    fn default() -> Self {
+        Self {
+            host: DEFAULT_HOST.to_string(),
+            port: DEFAULT_PORT,
+            allow_network: false,
+            shutdown_grace_secs: DEFAULT_SHUTDOWN_GRACE_SECS,
+            python_path: None,
+            launcher: BackendLauncher::default(),
+            env: HashMap::new(),
+            env_file: None,
+            workers: 1,
+            reload: false,
+            process_priority: ProcessPriority::default(),
+            tls_cert: None,
+            tls_key: None,
+            health_path: "/health".to_string(),
+            network_wait_secs: DEFAULT_NETWORK_WAIT_SECS,
+            min_python: DEFAULT_MIN_PYTHON.to_string(),
+        }
+    }
+}
+
+impl BackendConfig {
+    // The backend of the active profile in the user's settings
+    pub fn load<R: Runtime>(app_handle: &AppHandle<R>) -> Self {
+        crate::settings::load(app_handle).active_backend().clone()
+    }
+
+    // Environment variables take precedence over the settings file, which
+    // takes precedence over the defaults
+    fn apply_env_overrides(&mut self) -> Result<(), BackendError> {
+        if let Ok(host) = std::env::var(HOST_ENV) {
+            self.host = host;
+        }
+
+        if let Ok(port) = std::env::var(PORT_ENV) {
+            self.port = port.trim().parse().map_err(|_| {
+                BackendError::InvalidConfig(format!(
+                    "{} must be a port number, got {:?} (it overrides the port in settings.json, \
+                     which overrides the default {})",
+                    PORT_ENV, port, DEFAULT_PORT
+                ))
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), BackendError> {
+        if self.workers == 0 {
+            return Err(BackendError::InvalidConfig(
+                "Backend config needs at least 1 worker".to_string(),
+            ));
+        }
+        // uvicorn refuses to combine the two
+        if self.reload && self.workers > 1 {
+            return Err(BackendError::InvalidConfig(format!(
+                "Backend config can't use reload with {} workers; set workers to 1 or turn reload off",
+                self.workers
+            )));
+        }
+
+        // uvicorn takes IPv6 literals bare (--host ::1); brackets only go in URLs
+        if !self.host.eq_ignore_ascii_case("localhost") && self.host.parse::<IpAddr>().is_err() {
+            return Err(BackendError::InvalidConfig(format!(
+                "Backend host {:?} must be localhost or an IP address such as 127.0.0.1 or ::1",
+                self.host
+            )));
+        }
+
+        if !self.health_path.starts_with('/') {
+            return Err(BackendError::InvalidConfig(format!(
+                "Backend health path {:?} must start with /",
+                self.health_path
+            )));
+        }
+
+        if parse_python_version(&self.min_python).is_none() {
+            return Err(BackendError::InvalidConfig(format!(
+                "Backend minPython {:?} must be a version such as {}",
+                self.min_python, DEFAULT_MIN_PYTHON
+            )));
+        }
+
+        match (&self.tls_cert, &self.tls_key) {
+            (Some(cert), Some(key)) => {
+                for (label, path) in [("certificate", cert), ("key", key)] {
+                    if !path.is_file() {
+                        return Err(BackendError::InvalidConfig(format!(
+                            "TLS {} {:?} does not exist",
+                            label, path
+                        )));
+                    }
+                }
+            }
+            (Some(_), None) | (None, Some(_)) => {
+                return Err(BackendError::InvalidConfig(
+                    "HTTPS needs both tlsCert and tlsKey to be set".to_string(),
+                ));
+            }
+            (None, None) => {}
+        }
+
+        Ok(())
+    }
+
+    fn scheme(&self) -> &'static str {
+        if self.tls_cert.is_some() && self.tls_key.is_some() {
+            "https"
+        } else {
+            "http"
+        }
+    }
+
+    // Whether the backend would only be reachable from this machine
+    fn is_loopback(&self) -> bool {
+        self.host.eq_ignore_ascii_case("localhost")
+            || self.host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+    }
+
+    fn is_ipv6(&self) -> bool {
+        self.host.parse::<IpAddr>().is_ok_and(|ip| ip.is_ipv6())
+    }
+
+    // host:port as it appears in a URL, with IPv6 literals bracketed
+    fn address(&self) -> String {
+        if self.is_ipv6() {
+            format!("[{}]:{}", self.host, self.port)
+        } else {
+            format!("{}:{}", self.host, self.port)
+        }
+    }
+
+    // Where clients on this machine reach the backend, e.g.
+    // http://127.0.0.1:8000 or http://[::1]:8000. An unspecified bind address
+    // (0.0.0.0 or ::) can't be dialled everywhere, so it becomes the matching
+    // loopback address; any other host, a LAN address included, is used as is.
+    pub fn client_url(&self) -> String {
+        let address = match self.host.parse::<IpAddr>() {
+            Ok(IpAddr::V4(ip)) if ip.is_unspecified() => format!("127.0.0.1:{}", self.port),
+            Ok(IpAddr::V6(ip)) if ip.is_unspecified() => format!("[::1]:{}", self.port),
+            _ => self.address(),
+        };
+        format!("{}://{}", self.scheme(), address)
+    }
+
+    // Fall back to 127.0.0.1 for a network host that wasn't explicitly allowed.
+    // Returns whether the backend will still be exposed to the network.
+    fn restrict_to_loopback(&mut self) -> bool {
+        if self.is_loopback() {
+            return false;
+        }
+
+        if !self.allow_network {
+            eprintln!(
+                "Warning: Backend host {} is not a loopback address; using {} instead. \
+                 Set allowNetwork to serve on the network.",
+                self.host, DEFAULT_HOST
+            );
+            self.host = DEFAULT_HOST.to_string();
+            return false;
+        }
+
+        eprintln!(
+            "Warning: ************************************************************\n\
+             Warning: The backend is bound to {} and reachable from the network.\n\
+             Warning: Its API has no authentication - anyone who can reach this\n\
+             Warning: machine can use it.\n\
+             Warning: ************************************************************",
+            self.host
+        );
+        true
+    }
+
+    // uvicorn options that follow the app path; the bundled backend accepts the same ones
+    fn uvicorn_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--host".to_string(),
+            self.host.clone(),
+            "--port".to_string(),
+            self.port.to_string(),
+        ];
+        if self.workers > 1 {
+            args.extend(["--workers".to_string(), self.workers.to_string()]);
+        }
+        if self.reload {
+            args.push("--reload".to_string());
+        }
+        if let (Some(cert), Some(key)) = (&self.tls_cert, &self.tls_key) {
+            args.push("--ssl-certfile".to_string());
+            args.push(cert.to_string_lossy().into_owned());
+            args.push("--ssl-keyfile".to_string());
+            args.push(key.to_string_lossy().into_owned());
+        }
+        args
+    }
+}
+
+// Config of the most recently started backend, or the defaults if none was started
+pub fn active_config<R: Runtime>(app_handle: &AppHandle<R>) -> BackendConfig {
+    manager(app_handle).active_config()
+}
+
+impl BackendManager {
+    // Spawn the backend, or adopt one already serving on the configured port,
+    // and return the port it serves on, which may be above the configured one.
+    // Returns once it is spawned; wait_for_backend_ready waits for it to serve.
+    pub fn start<R: Runtime>(&self, app_handle: &AppHandle<R>) -> Result<u16, BackendError> {
+        println!("Starting FastAPI backend server...");
+
+        if self.is_running() {
+            return Err(BackendError::AlreadyRunning);
+        }
+
+        let trial = self.trial_config.lock().unwrap().clone();
+        let mut config = trial.unwrap_or_else(|| BackendConfig::load(app_handle));
+        config.apply_env_overrides()?;
+        // The command line beats both the environment and the settings file
+        let cli = crate::cli::args();
+        if let Some(port) = cli.port {
+            config.port = port;
+        }
+        config.validate()?;
+        let exposed = config.restrict_to_loopback();
+        self.start_failed.store(false, Ordering::SeqCst);
+        self.start_error.lock().unwrap().take();
+        self.paused.store(false, Ordering::SeqCst);
+
+        if let Some(python_path) = &config.python_path {
+            validate_interpreter(python_path)?;
+        }
+        config.launcher.check_available()?;
+        wait_for_network(&config);
+
+        // Otherwise an orphan of ours would be mistaken for an external backend below
+        reap_orphan(app_handle, &config);
+
+        // Reuse a backend that is already serving on this port, e.g. one started by hand
+        if probe_health(&config) {
+            println!(
+                "✓ Found a running backend on {}, not starting another",
+                config.address()
+            );
+
+            let port = config.port;
+            self.ready.store(false, Ordering::SeqCst);
+            self.externally_managed.store(true, Ordering::SeqCst);
+            *self.process.lock().unwrap() = None;
+            *self.config.lock().unwrap() = Some(config);
+
+            status_changed(app_handle, BACKEND_STARTING);
+            return Ok(port);
+        }
+
+        // Move up to the next free port if something else holds the configured one
+        let port = free_port(&config)?;
+        if port != config.port {
+            println!("Port {} is in use, starting the backend on {} instead", config.port, port);
+            config.port = port;
+        }
+
+        let app_dir = app_handle
+            .path()
+            .app_data_dir()
+            .unwrap_or_else(|_| std::path::PathBuf::from("."));
+        std::fs::create_dir_all(&app_dir).map_err(BackendError::SpawnFailed)?;
+
+        let settings = crate::settings::load(app_handle);
+        let bundled = bundled_backend(app_handle, &config)?;
+        let backend_dir = resolve_backend_dir(app_handle, &settings, bundled.is_some(), &app_dir)?;
+
+        println!("Backend directory: {:?}", backend_dir);
+        let file_env = config
+            .env_file
+            .as_ref()
+            .map(|path| load_env_file(&backend_dir.join(path)))
+            .unwrap_or_default();
+        if !config.env.is_empty() {
+            println!("Backend environment: {}", masked_env(&config.env));
+        }
+
+        // An old Python, whether first on PATH or in the conda or poetry
+        // environment, would only fail once uvicorn imports
+        if bundled.is_none() {
+            interpreter_version(&config.launcher, &config)?;
+        }
+
+        // Start uvicorn server, either frozen into the bundled executable or
+        // through the Python interpreter
+        let launcher = bundled.is_none().then(|| config.launcher.clone());
+        let mut command = match bundled {
+            Some(executable) => {
+                println!("Using bundled backend {:?}", executable);
+                Command::new(executable)
+            }
+            None => {
+                let mut command = config.launcher.command(&config);
+                // Import the app from the backend dir itself rather than relying on
+                // the working directory being on sys.path
+                command.arg("--app-dir").arg(&backend_dir);
+                command
+            }
+        };
+        // The bundled executable only takes the options uvicorn_args passes
+        let passthrough: &[String] = if launcher.is_none() && !cli.uvicorn_args.is_empty() {
+            eprintln!(
+                "Warning: Ignoring uvicorn arguments {:?}, the bundled backend doesn't accept them",
+                cli.uvicorn_args
+            );
+            &[]
+        } else {
+            &cli.uvicorn_args
+        };
+        let log_level = settings.log_level.filter();
+        command
+            .args(config.uvicorn_args())
+            .args(["--log-level", uvicorn_log_level(log_level)])
+            .args(passthrough)
+            .current_dir(&backend_dir)
+            .envs(&file_env)
+            .envs(&config.env)
+            .envs(cli.env.iter().map(|(name, value)| (name, value)))
+            .env(APP_DATA_DIR_ENV, &app_dir)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        isolate_process(&mut command);
+
+        let mut process = command.spawn().map_err(|e| match (&launcher, e.kind()) {
+            (Some(launcher), io::ErrorKind::NotFound) => launcher.not_found(),
+            _ => BackendError::SpawnFailed(e),
+        })?;
+
+        crate::backend_log::capture(app_handle, &mut process);
+
+        // Workers spawned from here on inherit it
+        if let Err(e) = set_priority(&process, config.process_priority) {
+            eprintln!(
+                "Warning: Failed to set backend priority to {:?}: {}",
+                config.process_priority, e
+            );
+        }
+
+        let pid = process.id();
+        println!("✓ Backend started with PID: {} on {}", pid, config.address());
+        write_pid_file(app_handle, pid, config.port);
+
+        if exposed {
+            let payload = ExposedPayload {
+                host: config.host.clone(),
+                port: config.port,
+            };
+            if let Err(e) = app_handle.emit(BACKEND_EXPOSED, payload) {
+                eprintln!("Failed to emit {}: {}", BACKEND_EXPOSED, e);
+            }
+        }
+
+        self.track(process, config);
+
+        status_changed(app_handle, BACKEND_STARTING);
+
+        Ok(port)
+    }
+
+    // Take charge of a freshly spawned backend, which counts as Starting until
+    // the readiness probe passes
+    fn track(&self, process: Child, config: BackendConfig) {
+        self.ready.store(false, Ordering::SeqCst);
+        self.externally_managed.store(false, Ordering::SeqCst);
+        *self.process.lock().unwrap() = Some(BackendGuard::new(process));
+        *self.spawned_at.lock().unwrap() = Some(Instant::now());
+        *self.config.lock().unwrap() = Some(config);
+    }
+
+    // Stop the current backend (if any), start a fresh one and wait for it to be ready
+    pub fn restart<R: Runtime>(&self, app_handle: &AppHandle<R>) -> Result<u16, BackendError> {
+        let _lifecycle = self.lifecycle.lock().unwrap();
+        let count = self.record_restart();
+        println!("Restarting backend server (restart {})...", count);
+        status_changed(app_handle, BACKEND_RESTARTING);
+
+        // stop only returns once the old process has exited (or been killed),
+        // so the new one never races it for the port
+        self.stop(app_handle);
+
+        let result = self.start(app_handle).and_then(|port| {
+            wait_for_backend_ready(app_handle)?;
+            Ok(port)
+        });
+
+        if let Err(e) = &result {
+            // Don't leave a backend that never became ready running unnoticed
+            self.stop(app_handle);
+            *self.start_error.lock().unwrap() = Some(e.to_string());
+            mark_start_failed(app_handle);
+        }
+        result
+    }
+
+    pub fn stop<R: Runtime>(&self, app_handle: &AppHandle<R>) {
+        println!("Stopping backend server...");
+
+        self.ready.store(false, Ordering::SeqCst);
+        self.start_failed.store(false, Ordering::SeqCst);
+        self.paused.store(false, Ordering::SeqCst);
+
+        if self.externally_managed.swap(false, Ordering::SeqCst) {
+            println!("Leaving the externally managed backend running");
+        }
+        // A stopped process only acts on SIGTERM once it is continued
+        self.continue_process();
+
+        if self.stop_process() {
+            remove_pid_file(app_handle);
+        }
+
+        status_changed(app_handle, BACKEND_STOPPED);
+    }
+
+    // Stop the tracked process with SIGSTOP, if it is ours and running, and
+    // report whether it was suspended
+    #[cfg(unix)]
+    fn suspend_process(&self) -> bool {
+        let Some(pid) = self.running_pid() else {
+            return false;
+        };
+        // The whole group, so uvicorn's workers stop too
+        if !signal_pid_group(pid, libc::SIGSTOP) {
+            eprintln!("Failed to suspend backend: {}", io::Error::last_os_error());
+            return false;
+        }
+        self.suspended.store(true, Ordering::SeqCst);
+        true
+    }
+
+    #[cfg(not(unix))]
+    fn suspend_process(&self) -> bool {
+        false
+    }
+
+    // Let a suspended process run again, reporting whether one was suspended
+    // and is still there
+    #[cfg(unix)]
+    fn continue_process(&self) -> bool {
+        if !self.suspended.swap(false, Ordering::SeqCst) {
+            return false;
+        }
+        self.running_pid()
+            .is_some_and(|pid| signal_pid_group(pid, libc::SIGCONT))
+    }
+
+    #[cfg(not(unix))]
+    fn continue_process(&self) -> bool {
+        self.suspended.store(false, Ordering::SeqCst);
+        false
+    }
+
+    // Pid of the process we spawned, while it hasn't exited
+    #[cfg(unix)]
+    fn running_pid(&self) -> Option<u32> {
+        self.process
+            .lock()
+            .unwrap()
+            .as_mut()
+            .and_then(|process| matches!(process.try_wait(), Ok(None)).then(|| process.id()))
+    }
+
+    // Terminate the tracked process, if there is one, and report whether there was
+    fn stop_process(&self) -> bool {
+        let grace = Duration::from_secs(self.active_config().shutdown_grace_secs);
+
+        // Take the handle first so the lock isn't held during the grace period
+        let process = self.process.lock().unwrap().take();
+        let Some(mut process) = process else {
+            return false;
+        };
+        match terminate(&mut process, grace) {
+            Ok(_) => println!("✓ Backend stopped"),
+            Err(e) => eprintln!("Failed to stop backend: {}", e),
+        }
+        true
+    }
+}
+
+// The free functions below are kept for callers that only have an app handle
+
+pub fn start_backend_server<R: Runtime>(app_handle: &AppHandle<R>) -> Result<u16, BackendError> {
+    manager(app_handle).start(app_handle)
+}
+
+pub fn restart_backend_server<R: Runtime>(app_handle: &AppHandle<R>) -> Result<u16, BackendError> {
+    manager(app_handle).restart(app_handle)
+}
+
+pub fn stop_backend_server<R: Runtime>(app_handle: &AppHandle<R>) {
+    manager(app_handle).stop(app_handle)
+}
+
+// Give the backend its own process group so stopping it reaches every worker
+fn isolate_process(command: &mut Command) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+
+    // Run headless instead of opening a console window next to the app.
+    // The piped stdout/stderr are unaffected, so logs are still captured.
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+}
+
+#[cfg(unix)]
+fn set_priority(process: &Child, priority: ProcessPriority) -> io::Result<()> {
+    let nice = match priority {
+        ProcessPriority::Low => 10,
+        ProcessPriority::Normal => return Ok(()),
+        ProcessPriority::High => -5,
+    };
+
+    // The backend leads its own group, so this covers workers it already forked
+    // SAFETY: setpriority has no memory-safety preconditions
+    let result = unsafe { libc::setpriority(libc::PRIO_PGRP as _, process.id() as _, nice) };
+    if result == 0 {
+        return Ok(());
+    }
+
+    let e = io::Error::last_os_error();
+    if priority == ProcessPriority::High && e.kind() == io::ErrorKind::PermissionDenied {
+        println!("High backend priority needs root on this system, keeping it at normal");
+        return Ok(());
+    }
+    Err(e)
+}
+
+#[cfg(windows)]
+fn set_priority(process: &Child, priority: ProcessPriority) -> io::Result<()> {
+    use std::os::windows::io::AsRawHandle;
+
+    // Above normal rather than HIGH_PRIORITY_CLASS, which can starve the UI itself
+    const BELOW_NORMAL_PRIORITY_CLASS: u32 = 0x0000_4000;
+    const ABOVE_NORMAL_PRIORITY_CLASS: u32 = 0x0000_8000;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn SetPriorityClass(process: *mut std::ffi::c_void, priority_class: u32) -> i32;
+    }
+
+    let class = match priority {
+        ProcessPriority::Low => BELOW_NORMAL_PRIORITY_CLASS,
+        ProcessPriority::Normal => return Ok(()),
+        ProcessPriority::High => ABOVE_NORMAL_PRIORITY_CLASS,
+    };
+
+    // SAFETY: the handle belongs to the live child process we own
+    if unsafe { SetPriorityClass(process.as_raw_handle(), class) } != 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+fn pid_file_path<R: Runtime>(app_handle: &AppHandle<R>) -> Option<PathBuf> {
+    app_handle
+        .path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join(PID_FILE))
+}
+
+fn write_pid_file<R: Runtime>(app_handle: &AppHandle<R>, pid: u32, port: u16) {
+    let Some(path) = pid_file_path(app_handle) else {
+        return;
+    };
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(&path, format!("{}\n{}\n", pid, port)));
+    if let Err(e) = result {
+        eprintln!("Warning: Failed to write {:?}: {}", path, e);
+    }
+}
+
+fn remove_pid_file<R: Runtime>(app_handle: &AppHandle<R>) {
+    if let Some(path) = pid_file_path(app_handle) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+// Kill a backend left behind by a previous run that crashed, so it doesn't
+// hold the port. The recorded process is only killed if it is still a backend
+// and something answers our health check on its port; a reused pid belongs
+// to someone else.
+fn reap_orphan<R: Runtime>(app_handle: &AppHandle<R>, config: &BackendConfig) {
+    let Some(path) = pid_file_path(app_handle) else {
+        return;
+    };
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return;
+    };
+    let _ = std::fs::remove_file(&path);
+
+    let mut fields = contents.split_whitespace().map(str::parse::<u32>);
+    let (Some(Ok(pid)), Some(Ok(port))) = (fields.next(), fields.next()) else {
+        eprintln!("Warning: Ignoring malformed {:?}", path);
+        return;
+    };
+    let Ok(port) = u16::try_from(port) else {
+        return;
+    };
+
+    let orphan = BackendConfig {
+        port,
+        ..config.clone()
+    };
+    if !is_backend_process(pid) || !probe_health(&orphan) {
+        return;
+    }
+
+    println!("Stopping orphaned backend (PID {}) on port {}", pid, port);
+    if !kill_pid_tree(pid) {
+        eprintln!("Failed to stop orphaned backend (PID {})", pid);
+        return;
+    }
+
+    // Give the OS a moment to release the port before we try to bind it
+    let grace = Duration::from_secs(config.shutdown_grace_secs);
+    let started = Instant::now();
+    while port_taken(&orphan) && started.elapsed() < grace {
+        std::thread::sleep(EXIT_POLL_INTERVAL);
+    }
+}
+
+// Whether `pid` is running the bundled backend or uvicorn, however it was
+// launched; conda and poetry keep the uvicorn arguments on their command line
+fn is_backend_process(pid: u32) -> bool {
+    use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, UpdateKind};
+
+    let mut system = sysinfo::System::new();
+    let pid = sysinfo::Pid::from_u32(pid);
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        true,
+        ProcessRefreshKind::nothing()
+            .with_cmd(UpdateKind::Always)
+            .with_exe(UpdateKind::Always),
+    );
+    let Some(process) = system.process(pid) else {
+        return false;
+    };
+
+    let bundled = process
+        .exe()
+        .and_then(Path::file_stem)
+        .is_some_and(|name| name == "renai-backend");
+    bundled || process.cmd().iter().any(|arg| arg == "uvicorn")
+}
+
+// Whether a variable's name suggests its value must not be logged
+pub fn is_secret_env(name: &str) -> bool {
+    let upper = name.to_uppercase();
+    SECRET_ENV_MARKERS.iter().any(|marker| upper.contains(marker))
+}
+
+// Render env vars for logging, hiding the values of anything that looks secret
+fn masked_env(env: &HashMap<String, String>) -> String {
+    let mut vars: Vec<String> = env
+        .iter()
+        .map(|(name, value)| {
+            if is_secret_env(name) {
+                format!("{}=****", name)
+            } else {
+                format!("{}={}", name, value)
+            }
+        })
+        .collect();
+    vars.sort();
+    vars.join(", ")
+}
+
+// Variables from a .env file; a missing or unreadable file only warns, so the
+// backend still starts with the rest of its environment
+fn load_env_file(path: &Path) -> HashMap<String, String> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => {
+            let env = parse_env_file(&contents);
+            println!("Loaded {} from {:?}: {}", env.len(), path, masked_env(&env));
+            env
+        }
+        Err(e) => {
+            eprintln!("Warning: Failed to read env file {:?}: {}", path, e);
+            HashMap::new()
+        }
+    }
+}
+
+// KEY=VALUE lines as dotenv writes them, optionally prefixed with `export` and
+// with the value in quotes. Blank lines and # comments are skipped.
+fn parse_env_file(contents: &str) -> HashMap<String, String> {
+    let mut env = HashMap::new();
+
+    for (number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+
+        let Some((name, value)) = line.split_once('=') else {
+            eprintln!("Warning: Ignoring env file line {} without '='", number + 1);
+            continue;
+        };
+        let name = name.trim();
+        if name.is_empty() {
+            eprintln!("Warning: Ignoring env file line {} without a name", number + 1);
+            continue;
+        }
+
+        let value = value.trim();
+        let value = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) if value.len() > 1 && value.ends_with(quote) => {
+                &value[1..value.len() - 1]
+            }
+            // An unquoted value ends where a comment starts
+            _ => value.split(" #").next().unwrap_or_default().trim_end(),
+        };
+        env.insert(name.to_string(), value.to_string());
+    }
+    env
+}
+
+// uvicorn's name for the closest level to the app's own
+fn uvicorn_log_level(level: log::LevelFilter) -> &'static str {
+    match level {
+        log::LevelFilter::Off => "critical",
+        log::LevelFilter::Error => "error",
+        log::LevelFilter::Warn => "warning",
+        log::LevelFilter::Info => "info",
+        log::LevelFilter::Debug => "debug",
+        log::LevelFilter::Trace => "trace",
+    }
+}
+
+// Directory the backend runs in: the configured one, else the project root in
+// development, else the resource dir. The bundled executable carries its own
+// code, so it only needs a writable working dir.
+fn resolve_backend_dir<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    settings: &crate::settings::Settings,
+    bundled: bool,
+    app_dir: &Path,
+) -> Result<PathBuf, BackendError> {
+    let dir = match &settings.backend_dir {
+        Some(dir) => dir.clone(),
+        None if bundled => return Ok(app_dir.to_path_buf()),
+        // Known at build time, so it doesn't depend on where the app was launched from
+        None if cfg!(debug_assertions) => Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default(),
+        None => app_handle.path().resource_dir().map_err(|e| {
+            BackendError::InvalidConfig(format!("Failed to locate the resource directory: {}", e))
+        })?,
     };
 
-    // Get the resource directory or current directory
-    let app_dir = app_handle
+    if !dir.join("backend").join("__init__.py").is_file() {
+        return Err(BackendError::InvalidConfig(format!(
+            "Backend directory {:?} doesn't contain the backend package; set backendDir \
+             in settings.json to the folder that holds backend/",
+            dir
+        )));
+    }
+
+    Ok(dir)
+}
+
+// Release builds run the bundled executable so no system Python is needed,
+// unless an interpreter was configured explicitly. Debug builds always use Python.
+fn bundled_backend<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    config: &BackendConfig,
+) -> Result<Option<PathBuf>, BackendError> {
+    if cfg!(debug_assertions)
+        || config.python_path.is_some()
+        || config.launcher != BackendLauncher::Module
+    {
+        return Ok(None);
+    }
+
+    let path = app_handle
         .path()
-        .app_data_dir()
-        .unwrap_or_else(|_| std::path::PathBuf::from("."));
+        .resolve(BUNDLED_BACKEND, BaseDirectory::Resource)
+        .map_err(|e| {
+            BackendError::InvalidConfig(format!("Failed to locate the bundled backend: {}", e))
+        })?;
 
-    // In development, backend is in parent directory
-    // In production, it would be bundled
-    let backend_dir = if cfg!(debug_assertions) {
-        std::env::current_dir()
-            .unwrap()
-            .parent()
-            .unwrap()
-            .to_path_buf()
+    if !path.is_file() {
+        return Err(BackendError::InvalidConfig(format!(
+            "Bundled backend not found at {:?}; reinstall RenAI or set pythonPath in the backend settings",
+            path
+        )));
+    }
+
+    Ok(Some(path))
+}
+
+// Find Python executable
+fn python_command(config: &BackendConfig) -> PathBuf {
+    if let Some(python_path) = &config.python_path {
+        return python_path.clone();
+    }
+
+    if cfg!(target_os = "windows") {
+        PathBuf::from("python")
+    } else {
+        PathBuf::from("python3")
+    }
+}
+
+// Version of the interpreter a Python start would use, e.g. "Python 3.11.4".
+// One older than `min_python` can't run the backend.
+pub fn python_version(config: &BackendConfig) -> Result<String, BackendError> {
+    if let Some(python_path) = &config.python_path {
+        validate_interpreter(python_path)?;
+    }
+    interpreter_version(&BackendLauncher::Module, config)
+}
+
+// Version of the Python `launcher` runs, checked against config.min_python
+fn interpreter_version(
+    launcher: &BackendLauncher,
+    config: &BackendConfig,
+) -> Result<String, BackendError> {
+    let mut python = launcher.python(config);
+    // As it appears in errors, e.g. "python3" or "conda" "run" ... "python"
+    let name = format!("{:?}", python);
+    let output = python
+        .arg("--version")
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => launcher.not_found(),
+            _ => BackendError::SpawnFailed(e),
+        })?;
+
+    // Python 2 printed its version to stderr
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let version = if version.is_empty() {
+        String::from_utf8_lossy(&output.stderr).trim().to_string()
     } else {
-        app_dir.clone()
+        version
     };
 
-    println!("Backend directory: {:?}", backend_dir);
+    let found = version.strip_prefix("Python ").unwrap_or_default();
+    let parsed = parse_python_version(found);
+    if !output.status.success() || parsed.is_none() {
+        return Err(BackendError::InvalidConfig(format!(
+            "{} is not a usable Python interpreter ({})",
+            name,
+            if version.is_empty() { output.status.to_string() } else { version }
+        )));
+    }
+
+    if parsed < parse_python_version(&config.min_python) {
+        return Err(BackendError::PythonTooOld {
+            found: found.to_string(),
+            required: config.min_python.clone(),
+        });
+    }
+    Ok(version)
+}
+
+// MAJOR.MINOR of a version such as "3.9" or "3.13.0rc1"
+fn parse_python_version(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.trim().split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+// Folder a Python start would run the backend package from
+pub fn python_backend_dir<R: Runtime>(app_handle: &AppHandle<R>) -> Result<PathBuf, BackendError> {
+    // The app dir is only used by the bundled executable
+    resolve_backend_dir(app_handle, &crate::settings::load(app_handle), false, Path::new(""))
+}
+
+// Whether release builds will run the bundled executable, which needs no Python
+pub fn has_bundled_backend<R: Runtime>(app_handle: &AppHandle<R>) -> bool {
+    matches!(bundled_backend(app_handle, &BackendConfig::load(app_handle)), Ok(Some(_)))
+}
 
-    // Start uvicorn server
-    let process = Command::new(python_cmd)
-        .args(&[
-            "-m",
-            "uvicorn",
-            "backend.main:app",
-            "--host",
-            "127.0.0.1",
-            "--port",
-            "8000",
-        ])
-        .current_dir(&backend_dir)
-        .spawn()
-        .map_err(|e| format!("Failed to start backend: {}", e))?;
+// A configured interpreter must be an existing, executable file
+fn validate_interpreter(path: &Path) -> Result<(), BackendError> {
+    let invalid = |reason: String| {
+        BackendError::InvalidConfig(format!("Python interpreter {:?} {}", path, reason))
+    };
+
+    let metadata =
+        std::fs::metadata(path).map_err(|e| invalid(format!("is not usable: {}", e)))?;
 
-    let pid = process.id();
-    println!("✓ Backend started with PID: {}", pid);
+    if !metadata.is_file() {
+        return Err(invalid("is not a file".to_string()));
+    }
 
-    // Store process handle
-    *BACKEND_PROCESS.lock().unwrap() = Some(process);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if metadata.permissions().mode() & 0o111 == 0 {
+            return Err(invalid("is not executable".to_string()));
+        }
+    }
 
     Ok(())
 }
 
-pub fn stop_backend_server() {
-    println!("Stopping backend server...");
+// Start the backend and wait for it to be ready, retrying with exponential
+// backoff so a transient failure doesn't abort the launch. Returns the port
+// the backend serves on.
+pub fn start_backend_with_retries<R: Runtime>(
+    app_handle: &AppHandle<R>,
+) -> Result<u16, BackendError> {
+    let manager = manager(app_handle);
+    let _lifecycle = manager.lifecycle.lock().unwrap();
+    let mut failures = Vec::new();
+    let mut backoff = START_BACKOFF;
 
-    if let Some(mut process) = BACKEND_PROCESS.lock().unwrap().take() {
-        match process.kill() {
-            Ok(_) => println!("✓ Backend stopped"),
-            Err(e) => eprintln!("Failed to stop backend: {}", e),
+    for attempt in 1..=START_ATTEMPTS {
+        println!("Starting backend (attempt {}/{})", attempt, START_ATTEMPTS);
+
+        let result = manager.start(app_handle).and_then(|port| {
+            wait_for_backend_ready(app_handle)?;
+            Ok(port)
+        });
+        let e = match result {
+            Ok(port) => return Ok(port),
+            Err(e) => e,
+        };
+
+        eprintln!("Backend start attempt {}/{} failed: {}", attempt, START_ATTEMPTS, e);
+        if matches!(e, BackendError::AlreadyRunning) {
+            return Err(e);
+        }
+        let transient = e.is_transient();
+        failures.push(e);
+
+        // Don't leave a half-started process holding the port
+        manager.stop(app_handle);
+
+        // Retrying won't fix a missing interpreter or bad settings
+        if !transient {
+            break;
+        }
+        if attempt < START_ATTEMPTS {
+            std::thread::sleep(backoff);
+            backoff *= 2;
+        }
+    }
+
+    let e = BackendError::AttemptsExhausted(failures);
+    *manager.start_error.lock().unwrap() = Some(e.to_string());
+    mark_start_failed(app_handle);
+    Err(e)
+}
+
+// Try `config` without saving it, replacing the running backend. A config that
+// started is kept for later starts until take_trial_config; one that failed
+// is dropped again.
+pub fn start_trial_backend<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    config: BackendConfig,
+) -> Result<u16, BackendError> {
+    let manager = manager(app_handle);
+    *manager.trial_config.lock().unwrap() = Some(config);
+
+    stop_backend_server(app_handle);
+    let result = start_backend_with_retries(app_handle);
+    if result.is_err() {
+        manager.trial_config.lock().unwrap().take();
+    }
+    result
+}
+
+// The config start_trial_backend got serving, if any; starts go back to the settings
+pub fn take_trial_config<R: Runtime>(app_handle: &AppHandle<R>) -> Option<BackendConfig> {
+    manager(app_handle).trial_config.lock().unwrap().take()
+}
+
+fn mark_start_failed<R: Runtime>(app_handle: &AppHandle<R>) {
+    manager(app_handle).start_failed.store(true, Ordering::SeqCst);
+    status_changed(app_handle, BACKEND_CRASHED);
+}
+
+// How a pause takes the backend out of service
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseMode {
+    // Always stop it, which is the only way to free its memory and GPU
+    Stop,
+    // Suspend it if suspend_on_pause is set, else stop it; for a pause the
+    // user asked for
+    Preferred,
+}
+
+// Stop the backend and leave it stopped until it is resumed, e.g. to free the
+// port or GPU for a while without quitting the app. A preferred pause with
+// suspend_on_pause suspends it instead, which frees the CPU but keeps its
+// models in memory.
+pub fn pause_backend_server<R: Runtime>(app_handle: &AppHandle<R>, mode: PauseMode) {
+    let manager = manager(app_handle);
+    let _lifecycle = manager.lifecycle.lock().unwrap();
+    println!("Pausing backend server...");
+
+    let suspend =
+        mode == PauseMode::Preferred && crate::settings::load(app_handle).suspend_on_pause;
+    if suspend && manager.suspend_process() {
+        println!("✓ Backend suspended");
+    } else {
+        manager.stop(app_handle);
+    }
+    manager.paused.store(true, Ordering::SeqCst);
+    status_changed(app_handle, BACKEND_PAUSED);
+}
+
+// Start a paused backend again, or continue a suspended one
+pub fn resume_backend_server<R: Runtime>(app_handle: &AppHandle<R>) -> Result<u16, BackendError> {
+    println!("Resuming backend server...");
+
+    let manager = manager(app_handle);
+    {
+        let _lifecycle = manager.lifecycle.lock().unwrap();
+        if manager.continue_process() {
+            manager.paused.store(false, Ordering::SeqCst);
+            println!("✓ Backend resumed");
+            status_changed(app_handle, BACKEND_READY_EVENT);
+            return Ok(manager.active_config().port);
+        }
+        // It was stopped, or went away while suspended, so start a new one
+    }
+    start_backend_with_retries(app_handle)
+}
+
+pub fn is_paused<R: Runtime>(app_handle: &AppHandle<R>) -> bool {
+    manager(app_handle).is_paused()
+}
+
+// Restart the backend whenever it exits on its own. An intentional stop takes
+// the process handle, so it never shows up here as Crashed.
+pub fn spawn_watchdog<R: Runtime>(app_handle: AppHandle<R>) {
+    std::thread::spawn(move || {
+        let manager = manager(&app_handle);
+
+        loop {
+            std::thread::sleep(WATCHDOG_INTERVAL);
+
+            if manager.uptime().is_some_and(|uptime| uptime >= STABLE_UPTIME) {
+                manager.restarts.store(0, Ordering::SeqCst);
+            }
+
+            // A failed start is reported to whoever asked for it, not retried here
+            let Some(exit) = manager.exit_status() else {
+                continue;
+            };
+            if manager.start_failed.load(Ordering::SeqCst) {
+                continue;
+            }
+            // A start or restart is already under way and will report on itself
+            let Ok(_lifecycle) = manager.lifecycle.try_lock() else {
+                continue;
+            };
+
+            // Exiting with 0 means it shut down on its own terms, e.g. someone
+            // else sent it SIGTERM, which is a stop rather than a crash
+            if exit.success() {
+                println!("Backend exited cleanly, leaving it stopped");
+                *manager.last_exit.lock().unwrap() = Some(exit);
+                manager.process.lock().unwrap().take();
+                remove_pid_file(&app_handle);
+                status_changed(&app_handle, BACKEND_STOPPED);
+                continue;
+            }
+            report_crash(&app_handle, Some(exit));
+
+            let restarts = manager.restarts();
+            if restarts >= MAX_AUTO_RESTARTS {
+                eprintln!("Backend crashed {} times, giving up on restarts", restarts + 1);
+                let _ = app_handle.emit("backend-failed", restarts);
+                crate::dialogs::show_crash_loop(&app_handle, restarts + 1);
+                // Left alone until a start clears start_failed, and that
+                // backend is watched again with a fresh budget
+                manager.restarts.store(0, Ordering::SeqCst);
+                mark_start_failed(&app_handle);
+                continue;
+            }
+
+            let restarts = manager.restarts.fetch_add(1, Ordering::SeqCst) + 1;
+            manager.record_restart();
+            eprintln!(
+                "Backend exited unexpectedly, restarting ({}/{})",
+                restarts, MAX_AUTO_RESTARTS
+            );
+
+            if let Err(e) = start_backend_server(&app_handle) {
+                eprintln!("Failed to restart backend: {}", e);
+                continue;
+            }
+
+            let _ = app_handle.emit(
+                "backend-restarted",
+                RestartPayload {
+                    restarts,
+                    max_restarts: MAX_AUTO_RESTARTS,
+                    restart_count: manager.restart_count(),
+                    last_restart: manager.last_restart(),
+                },
+            );
+
+            match wait_for_backend_ready(&app_handle) {
+                // Don't leave one that never became ready wedged; one that
+                // exited is picked up again on the next tick
+                Err(e @ BackendError::StartupTimeout(_)) => {
+                    eprintln!("{}", e);
+                    manager.stop(&app_handle);
+                    *manager.start_error.lock().unwrap() = Some(e.to_string());
+                    mark_start_failed(&app_handle);
+                }
+                Err(e) => eprintln!("{}", e),
+                Ok(()) => {}
+            }
+        }
+    });
+}
+
+// Poll the backend's health endpoint until it answers 2xx or the startup
+// timeout elapses. Cleaning up after a backend that failed is left to the caller.
+pub fn wait_for_backend_ready<R: Runtime>(app_handle: &AppHandle<R>) -> Result<(), BackendError> {
+    let manager = manager(app_handle);
+    let config = manager.active_config();
+    let timeout = Duration::from_secs(crate::settings::load(app_handle).startup_timeout_secs);
+    // Measured from spawn, so a slow start before the first probe still counts
+    let started = manager.spawned_at.lock().unwrap().unwrap_or_else(Instant::now);
+
+    loop {
+        if probe_health(&config) {
+            println!("✓ Backend ready after {:.1?}", started.elapsed());
+            manager.ready.store(true, Ordering::SeqCst);
+            status_changed(app_handle, BACKEND_READY_EVENT);
+            return Ok(());
+        }
+
+        if !manager.is_running() {
+            eprintln!("Backend exited after {:.1?} before becoming ready", started.elapsed());
+            report_crash(app_handle, manager.exit_status());
+            // The usual reason uvicorn quits right away is a taken port
+            if port_taken(&config) {
+                return Err(BackendError::PortInUse(config.port));
+            }
+            return Err(BackendError::ExitedEarly);
+        }
+
+        if started.elapsed() >= timeout {
+            return Err(BackendError::StartupTimeout(timeout));
+        }
+
+        std::thread::sleep(READY_POLL_INTERVAL);
+    }
+}
+
+// Wait until the host's address can be bound at all, which it can't while
+// the network stack is still coming back after a wake. Gives up quietly after
+// `network_wait_secs`, leaving the start to report whatever then fails.
+fn wait_for_network(config: &BackendConfig) {
+    let timeout = Duration::from_secs(config.network_wait_secs);
+    let started = Instant::now();
+    let mut waiting = false;
+
+    while !port_free(&config.host, 0) {
+        if started.elapsed() >= timeout {
+            eprintln!(
+                "Warning: {} is still unavailable after {:?}, starting anyway",
+                config.host, timeout
+            );
+            return;
+        }
+        if !waiting {
+            println!("Waiting for {} to become available...", config.host);
+            waiting = true;
+        }
+        std::thread::sleep(NETWORK_POLL_INTERVAL);
+    }
+
+    if waiting {
+        println!("✓ {} is available", config.host);
+    }
+}
+
+fn port_taken(config: &BackendConfig) -> bool {
+    !port_free(&config.host, config.port)
+}
+
+fn port_free(host: &str, port: u16) -> bool {
+    TcpListener::bind((host, port)).is_ok()
+}
+
+// The configured port if it can be bound, otherwise the first free one above it
+fn free_port(config: &BackendConfig) -> Result<u16, BackendError> {
+    let last = config.port.saturating_add(PORT_SCAN_RANGE);
+    (config.port..=last)
+        .find(|&port| port_free(&config.host, port))
+        .ok_or(BackendError::PortInUse(config.port))
+}
+
+// Send a single GET to the configured health path and check for a 2xx status
+pub fn probe_health(config: &BackendConfig) -> bool {
+    matches!(http_get(config, &config.health_path), Some((200..=299, _)))
+}
+
+// Version reported by the running backend's /version endpoint
+pub fn backend_version<R: Runtime>(app_handle: &AppHandle<R>) -> Option<String> {
+    let manager = manager(app_handle);
+    // A suspended backend would only let the request time out
+    if matches!(
+        manager.status(),
+        BackendStatus::Stopped | BackendStatus::Crashed | BackendStatus::Suspended { .. }
+    ) {
+        return None;
+    }
+
+    let (status, body) = http_get(&manager.active_config(), "/version")?;
+    if status != 200 {
+        return None;
+    }
+
+    let json: serde_json::Value = serde_json::from_str(&body).ok()?;
+    json.get("version")?.as_str().map(str::to_string)
+}
+
+// Ask the backend to save its state before the app quits. One that doesn't
+// confirm in time is stopped anyway, so quitting never hangs on it.
+pub fn save_backend_state<R: Runtime>(app_handle: &AppHandle<R>) {
+    let manager = manager(app_handle);
+    // Nothing to save before it is ready, and an external backend keeps running
+    if !matches!(manager.status(), BackendStatus::Running { .. }) {
+        return;
+    }
+
+    match http_post(&manager.active_config(), "/save", None, SAVE_TIMEOUT) {
+        Some(200..=299) => println!("✓ Backend saved its state"),
+        Some(status) => eprintln!("Warning: Backend failed to save its state (HTTP {})", status),
+        None => eprintln!(
+            "Warning: Backend didn't confirm saving within {:?}, stopping it anyway",
+            SAVE_TIMEOUT
+        ),
+    }
+}
+
+// Switch a running backend to `level`, through its /log-level endpoint if it
+// has one and otherwise by restarting it, since uvicorn only reads --log-level
+// at spawn. A stopped backend picks the level up from the settings when it starts.
+pub fn apply_log_level<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    level: LogLevel,
+) -> Result<(), BackendError> {
+    let manager = manager(app_handle);
+    let external = match manager.status() {
+        BackendStatus::Running { .. } => false,
+        BackendStatus::External { .. } => true,
+        _ => return Ok(()),
+    };
+
+    let body = serde_json::json!({ "level": level });
+    match http_post(&manager.active_config(), "/log-level", Some(&body), PROBE_TIMEOUT) {
+        Some(200..=299) => {
+            println!("✓ Backend log level set to {:?}", level);
+            Ok(())
+        }
+        // Someone else's backend isn't ours to restart
+        _ if external => {
+            eprintln!("Warning: The external backend didn't accept the new log level");
+            Ok(())
+        }
+        _ => {
+            println!("Backend can't change its log level while running, restarting it");
+            manager.restart(app_handle).map(|_| ())
+        }
+    }
+}
+
+// Blocking GET against the backend over HTTP or HTTPS, returning the status
+// code and body. Must not be called from an async task.
+fn http_get(config: &BackendConfig, path: &str) -> Option<(u16, String)> {
+    let response = http_client()?
+        .get(format!("{}{}", config.client_url(), path))
+        .timeout(PROBE_TIMEOUT)
+        .send()
+        .ok()?;
+    let status = response.status().as_u16();
+    Some((status, response.text().ok()?))
+}
+
+// Blocking POST with an optional JSON body, returning the status code. Must
+// not be called from an async task.
+fn http_post(
+    config: &BackendConfig,
+    path: &str,
+    body: Option<&serde_json::Value>,
+    timeout: Duration,
+) -> Option<u16> {
+    let mut request = http_client()?
+        .post(format!("{}{}", config.client_url(), path))
+        .timeout(timeout);
+    if let Some(body) = body {
+        request = request
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string());
+    }
+    let response = request.send().ok()?;
+    Some(response.status().as_u16())
+}
+
+// Built once and shared, so probes reuse connections instead of each setting
+// up a client; every request sets its own timeout
+fn http_client() -> Option<&'static reqwest::blocking::Client> {
+    static CLIENT: OnceLock<Option<reqwest::blocking::Client>> = OnceLock::new();
+    CLIENT
+        .get_or_init(|| {
+            reqwest::blocking::Client::builder()
+                // It is our own backend on a known address, typically with a self-signed cert
+                .danger_accept_invalid_certs(true)
+                .no_proxy()
+                .build()
+                .ok()
+        })
+        .as_ref()
+}
+
+// The async counterpart of http_client, for requests proxied from the frontend
+fn proxy_client() -> Result<&'static reqwest::Client, String> {
+    static CLIENT: OnceLock<Result<reqwest::Client, String>> = OnceLock::new();
+    CLIENT
+        .get_or_init(|| {
+            reqwest::Client::builder()
+                .danger_accept_invalid_certs(true)
+                .no_proxy()
+                .build()
+                .map_err(|e| format!("Failed to create HTTP client: {}", e))
+        })
+        .as_ref()
+        .map_err(Clone::clone)
+}
+
+// Ask the process tree to exit, then force-kill it if it is still alive after `grace`
+fn terminate(process: &mut Child, grace: Duration) -> std::io::Result<()> {
+    if process.try_wait()?.is_some() {
+        // The launcher is gone but its workers may still hold the port
+        kill_tree(process);
+        return Ok(());
+    }
+
+    if request_shutdown(process) {
+        let started = Instant::now();
+        while started.elapsed() < grace {
+            if process.try_wait()?.is_some() {
+                return Ok(());
+            }
+            std::thread::sleep(EXIT_POLL_INTERVAL);
         }
+        eprintln!("Backend did not exit within {:?}, killing it", grace);
     }
+
+    if !kill_tree(process) {
+        process.kill()?;
+    }
+    process.wait()?;
+
+    Ok(())
+}
+
+// Ask the backend to exit on its own, reporting whether the request was
+// delivered and is worth waiting for
+#[cfg(unix)]
+fn request_shutdown(process: &Child) -> bool {
+    // SIGTERM lets uvicorn run its shutdown handlers
+    signal_group(process, libc::SIGTERM)
+}
+
+// Started with CREATE_NO_WINDOW, the backend has neither a window to close
+// nor a console we share to send Ctrl+Break to, so taskkill without /F can't
+// reach it either. Waiting out the grace period would only delay the kill;
+// quitting asks it to save its state first instead (save_backend_state).
+#[cfg(windows)]
+fn request_shutdown(_process: &Child) -> bool {
+    false
+}
+
+// Force-kill the backend and every process it spawned
+fn kill_tree(process: &Child) -> bool {
+    kill_pid_tree(process.id())
+}
+
+#[cfg(unix)]
+fn kill_pid_tree(pid: u32) -> bool {
+    signal_pid_group(pid, libc::SIGKILL)
 }
 
-// Check if backend is running
-pub fn is_backend_running() -> bool {
-    BACKEND_PROCESS
-        .lock()
-        .unwrap()
-        .as_mut()
-        .map(|p| p.try_wait().ok().flatten().is_none())
+#[cfg(windows)]
+fn kill_pid_tree(pid: u32) -> bool {
+    Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/T", "/F"])
+        .status()
+        .map(|status| status.success())
         .unwrap_or(false)
 }
+
+#[cfg(unix)]
+fn signal_group(process: &Child, signal: libc::c_int) -> bool {
+    signal_pid_group(process.id(), signal)
+}
+
+// The backend leads its own process group, so its pid is also the group id
+#[cfg(unix)]
+fn signal_pid_group(pid: u32, signal: libc::c_int) -> bool {
+    // SAFETY: kill has no memory-safety preconditions
+    unsafe { libc::kill(-(pid as libc::pid_t), signal) == 0 }
+}
+
+// Reflect a lifecycle transition in the tray and tell the frontend about it
+fn status_changed<R: Runtime>(app_handle: &AppHandle<R>, event: &str) {
+    emit_status(app_handle, event, None);
+}
+
+// Report a backend that died on its own, with how it exited and its last output
+fn report_crash<R: Runtime>(app_handle: &AppHandle<R>, exit: Option<ExitStatus>) {
+    match exit {
+        Some(exit) => eprintln!("Backend crashed: {}", exit),
+        None => eprintln!("Backend crashed"),
+    }
+    if exit.is_some() {
+        *manager(app_handle).last_exit.lock().unwrap() = exit;
+    }
+
+    let crash = CrashDetails {
+        exit_code: exit.and_then(|exit| exit.code()),
+        log_tail: crate::backend_log::tail(app_handle, CRASH_LOG_LINES).ok(),
+    };
+    emit_status(app_handle, BACKEND_CRASHED, Some(crash));
+}
+
+fn emit_status<R: Runtime>(app_handle: &AppHandle<R>, event: &str, crash: Option<CrashDetails>) {
+    let manager = manager(app_handle);
+    let state = manager.state();
+    crate::tray::update_tray_status(app_handle, &state.status);
+
+    let payload = LifecyclePayload {
+        port: manager.active_config().port,
+        timestamp: now_millis(),
+        crash,
+    };
+    if let Err(e) = app_handle.emit(event, payload) {
+        eprintln!("Failed to emit {}: {}", event, e);
+    }
+    let _ = app_handle.emit(BACKEND_STATE_EVENT, state);
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+// Kept for callers that only have an app handle; commands take State<BackendManager>
+pub fn backend_status<R: Runtime>(app_handle: &AppHandle<R>) -> BackendStatus {
+    manager(app_handle).status()
+}
+
+#[tauri::command]
+pub fn get_backend_status(manager: State<'_, BackendManager>) -> BackendStatus {
+    manager.status()
+}
+
+// What backend-state last sent, for a page that loaded after it
+#[tauri::command]
+pub fn get_backend_state(manager: State<'_, BackendManager>) -> BackendState {
+    manager.state()
+}
+
+// Cheap liveness check. Async so that waiting on the process lock never holds
+// up the main thread; the lock is only ever held briefly.
+#[tauri::command]
+pub async fn backend_running(manager: State<'_, BackendManager>) -> Result<bool, String> {
+    Ok(manager.is_running())
+}
+
+// How long the backend we spawned has been running; None when it isn't, or
+// for an external backend whose start we never saw
+#[tauri::command]
+pub fn get_backend_uptime(manager: State<'_, BackendManager>) -> Option<Duration> {
+    manager.uptime()
+}
+
+pub fn backend_uptime<R: Runtime>(app_handle: &AppHandle<R>) -> Option<Duration> {
+    manager(app_handle).uptime()
+}
+
+// Restarts since launch, whether the watchdog or the user asked for them
+#[tauri::command]
+pub fn get_backend_restart_count(manager: State<'_, BackendManager>) -> u32 {
+    manager.restart_count()
+}
+
+// Port the backend is actually serving on, including one we reused rather than started
+#[tauri::command]
+pub fn get_backend_port(manager: State<'_, BackendManager>) -> u16 {
+    manager.active_config().port
+}
+
+// Base URL for the frontend to reach the backend at, with the right scheme
+#[tauri::command]
+pub fn get_backend_url(manager: State<'_, BackendManager>) -> String {
+    manager.active_config().client_url()
+}
+
+// Forward a request to the backend over loopback, so the frontend needs neither
+// CORS nor a network-reachable port
+#[tauri::command]
+pub async fn backend_request(
+    manager: State<'_, BackendManager>,
+    method: String,
+    path: String,
+    body: Option<String>,
+) -> Result<BackendResponse, String> {
+    if !path.starts_with('/') {
+        return Err(format!("Backend path {:?} must start with '/'", path));
+    }
+    let method = reqwest::Method::from_bytes(method.to_uppercase().as_bytes())
+        .map_err(|_| format!("Invalid HTTP method {:?}", method))?;
+
+    let config = manager.active_config();
+    let url = format!("{}{}", config.client_url(), path);
+    let mut request = proxy_client()?.request(method, url).timeout(PROXY_TIMEOUT);
+    if let Some(body) = body {
+        request = request
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body);
+    }
+
+    let response = request.send().await.map_err(|e| {
+        if e.is_connect() {
+            "Backend not running".to_string()
+        } else if e.is_timeout() {
+            format!("Backend didn't respond within {:?}", PROXY_TIMEOUT)
+        } else {
+            format!("Backend request failed: {}", e)
+        }
+    })?;
+
+    let status = response.status().as_u16();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read backend response: {}", e))?;
+    Ok(BackendResponse { status, body })
+}
+
+#[tauri::command]
+pub async fn restart_backend<R: Runtime>(app: AppHandle<R>) -> Result<u16, String> {
+    // Restarting blocks until the new backend is ready, so keep it off the async workers
+    tauri::async_runtime::spawn_blocking(move || restart_backend_server(&app))
+        .await
+        .map_err(|e| format!("Restart task failed: {}", e))?
+        .map_err(|e| e.to_string())
+}
+
+// Save a new log level and apply it to the running backend
+#[tauri::command]
+pub async fn set_backend_log_level<R: Runtime>(
+    app: AppHandle<R>,
+    level: LogLevel,
+) -> Result<(), String> {
+    let mut settings = crate::settings::load(&app);
+    settings.log_level = level;
+    crate::settings::save(&app, &settings)?;
+
+    // Posting and restarting block, so keep them off the async workers
+    tauri::async_runtime::spawn_blocking(move || apply_log_level(&app, level))
+        .await
+        .map_err(|e| format!("Log level task failed: {}", e))?
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn pause_backend<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    // Waits out the shutdown grace period, so keep it off the async workers
+    tauri::async_runtime::spawn_blocking(move || pause_backend_server(&app, PauseMode::Preferred))
+        .await
+        .map_err(|e| format!("Pause task failed: {}", e))
+}
+
+// Start a backend that is stopped or failed to start, retrying as on launch
+#[tauri::command]
+pub async fn start_backend<R: Runtime>(app: AppHandle<R>) -> Result<u16, String> {
+    tauri::async_runtime::spawn_blocking(move || start_backend_with_retries(&app))
+        .await
+        .map_err(|e| format!("Start task failed: {}", e))?
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn resume_backend<R: Runtime>(app: AppHandle<R>) -> Result<u16, String> {
+    tauri::async_runtime::spawn_blocking(move || resume_backend_server(&app))
+        .await
+        .map_err(|e| format!("Resume task failed: {}", e))?
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_file_lines_are_parsed() {
+        let env = parse_env_file(
+            "# API keys\n\
+             \n\
+             OPENAI_API_KEY=sk-123\n\
+             export MODEL = llama2  # the default\n\
+             GREETING=\"hello # world\"\n\
+             EMPTY=\n\
+             not a variable\n",
+        );
+
+        assert_eq!(env.len(), 4);
+        assert_eq!(env["OPENAI_API_KEY"], "sk-123");
+        assert_eq!(env["MODEL"], "llama2");
+        assert_eq!(env["GREETING"], "hello # world");
+        assert_eq!(env["EMPTY"], "");
+    }
+
+    #[test]
+    fn python_versions_compare_by_major_and_minor() {
+        let required = parse_python_version(DEFAULT_MIN_PYTHON);
+        assert_eq!(parse_python_version("3.13.0rc1"), Some((3, 13)));
+        assert!(parse_python_version("3.6.9") < required);
+        // Not a string comparison, where "3.10" would sort before "3.9"
+        assert!(parse_python_version("3.10.2") > required);
+        assert_eq!(parse_python_version("3"), None);
+        assert_eq!(parse_python_version("three.nine"), None);
+    }
+
+    #[test]
+    fn client_urls_dial_unspecified_hosts_on_loopback() {
+        let mut config = BackendConfig {
+            host: "::1".to_string(),
+            ..BackendConfig::default()
+        };
+        assert!(config.validate().is_ok());
+        assert!(config.is_loopback());
+        assert_eq!(config.client_url(), "http://[::1]:8000");
+        assert_eq!(config.uvicorn_args()[..2], ["--host", "::1"]);
+
+        config.host = "::".to_string();
+        assert_eq!(config.client_url(), "http://[::1]:8000");
+
+        config.host = "0.0.0.0".to_string();
+        assert_eq!(config.client_url(), "http://127.0.0.1:8000");
+
+        // A LAN address or name is dialled as configured
+        config.host = "192.168.1.5".to_string();
+        assert_eq!(config.client_url(), "http://192.168.1.5:8000");
+        config.host = "localhost".to_string();
+        assert_eq!(config.client_url(), "http://localhost:8000");
+
+        config.host = "[::1]".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dropping_guard_reaps_child() {
+        use std::os::unix::process::CommandExt;
+
+        let child = Command::new("sleep")
+            .arg("30")
+            .process_group(0)
+            .spawn()
+            .expect("failed to spawn sleep");
+        let pid = child.id() as libc::pid_t;
+
+        drop(BackendGuard::new(child));
+
+        // Once reaped the pid no longer exists, not even as a zombie
+        // SAFETY: kill with signal 0 only checks whether the process exists
+        let exists = unsafe { libc::kill(pid, 0) } == 0;
+        assert!(!exists, "sleep process {} was not reaped", pid);
+    }
+
+    // Track a stand-in for the backend and return its pid. It gets its own
+    // group like the real backend, so signals to the group reach it.
+    #[cfg(unix)]
+    fn spawn_sleeper(manager: &BackendManager) -> u32 {
+        let mut command = Command::new("sleep");
+        command.arg("30");
+        isolate_process(&mut command);
+        let child = command.spawn().expect("failed to spawn sleep");
+        let pid = child.id();
+        manager.track(child, BackendConfig::default());
+        pid
+    }
+
+    // Waits for a tracked stand-in process to exit on its own
+    #[cfg(unix)]
+    fn wait_for_exit(manager: &BackendManager) {
+        let started = Instant::now();
+        while manager.exit_status().is_none() {
+            assert!(started.elapsed() < Duration::from_secs(5), "process did not exit");
+            std::thread::sleep(EXIT_POLL_INTERVAL);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn manager_tracks_status_through_start_and_stop() {
+        let manager = BackendManager::default();
+        assert_eq!(manager.status(), BackendStatus::Stopped);
+
+        let pid = spawn_sleeper(&manager);
+        assert!(manager.is_running());
+        assert_eq!(manager.status(), BackendStatus::Starting);
+
+        manager.ready.store(true, Ordering::SeqCst);
+        assert_eq!(
+            manager.status(),
+            BackendStatus::Running {
+                pid,
+                port: DEFAULT_PORT
+            }
+        );
+
+        assert!(manager.stop_process());
+        assert!(!manager.is_running());
+        assert_eq!(manager.status(), BackendStatus::Stopped);
+        assert!(!manager.stop_process(), "nothing left to stop");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn suspended_backend_keeps_its_process() {
+        let manager = BackendManager::default();
+        let pid = spawn_sleeper(&manager);
+        manager.ready.store(true, Ordering::SeqCst);
+
+        assert!(manager.suspend_process());
+        assert_eq!(
+            manager.status(),
+            BackendStatus::Suspended {
+                pid,
+                port: DEFAULT_PORT
+            }
+        );
+
+        assert!(manager.continue_process());
+        assert!(matches!(manager.status(), BackendStatus::Running { .. }));
+        assert!(!manager.continue_process(), "nothing left to continue");
+        assert!(manager.stop_process());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn manager_tells_crashes_from_clean_exits() {
+        let manager = BackendManager::default();
+        let child = Command::new("sh").args(["-c", "exit 3"]).spawn().expect("failed to spawn sh");
+        manager.track(child, BackendConfig::default());
+        wait_for_exit(&manager);
+        assert_eq!(manager.status(), BackendStatus::Crashed);
+        assert_eq!(manager.exit_status().and_then(|exit| exit.code()), Some(3));
+
+        let child = Command::new("sh").args(["-c", "exit 0"]).spawn().expect("failed to spawn sh");
+        manager.track(child, BackendConfig::default());
+        wait_for_exit(&manager);
+        assert_eq!(manager.status(), BackendStatus::Stopped);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn terminate_stops_the_whole_group() {
+        // Stands in for uvicorn with a worker: the shell forks a second sleep,
+        // spawned the way start spawns the backend
+        let mut command = Command::new("sh");
+        command.args(["-c", "sleep 30 & sleep 30"]);
+        isolate_process(&mut command);
+        let mut child = command.spawn().expect("failed to spawn sh");
+        let pgid = child.id() as libc::pid_t;
+        // SAFETY: getpgid has no memory-safety preconditions
+        assert_eq!(unsafe { libc::getpgid(pgid) }, pgid, "backend leads its own group");
+
+        terminate(&mut child, Duration::from_secs(5)).expect("terminate failed");
+
+        // The orphaned worker is reaped by init, which may take a moment
+        let started = Instant::now();
+        // SAFETY: kill with signal 0 only checks whether the group has members
+        while unsafe { libc::kill(-pgid, 0) } == 0 {
+            assert!(
+                started.elapsed() < Duration::from_secs(5),
+                "process group {} outlived terminate",
+                pgid
+            );
+            std::thread::sleep(EXIT_POLL_INTERVAL);
+        }
+    }
+}
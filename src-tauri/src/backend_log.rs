@@ -0,0 +1,303 @@
+use serde::Serialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_opener::OpenerExt;
+
+const LOG_FILE_STEM: &str = "backend";
+
+// Most lines get_backend_log_tail will return, and how much it reads at a time
+const MAX_TAIL_LINES: usize = 2000;
+const TAIL_CHUNK_BYTES: u64 = 8 * 1024;
+
+// Live backend output for the frontend, sent as batches of lines
+const LOG_EVENT: &str = "backend-log";
+// Lines arriving this close together go out in one event
+const STREAM_BATCH_WINDOW: Duration = Duration::from_millis(50);
+
+type SharedLog = Arc<Mutex<RotatingLog>>;
+
+#[derive(Debug, Clone, Serialize)]
+struct LogLine {
+    // "stdout" or "stderr"
+    stream: &'static str,
+    line: String,
+}
+
+// Path of the captured backend output, under the app log dir
+pub fn log_path<R: Runtime>(app_handle: &AppHandle<R>) -> Option<PathBuf> {
+    app_handle
+        .path()
+        .app_log_dir()
+        .ok()
+        .map(|dir| dir.join(format!("{}.log", LOG_FILE_STEM)))
+}
+
+// Show backend.log in the OS file manager, or just its directory if nothing
+// has been logged yet
+pub fn reveal<R: Runtime>(app_handle: &AppHandle<R>) -> Result<(), String> {
+    let path = log_path(app_handle).ok_or("No app log directory")?;
+    let opener = app_handle.opener();
+
+    if path.exists() {
+        return opener
+            .reveal_item_in_dir(&path)
+            .map_err(|e| format!("Failed to reveal {:?}: {}", path, e));
+    }
+
+    let dir = path.parent().ok_or("No app log directory")?;
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    opener
+        .open_path(dir.to_string_lossy(), None::<&str>)
+        .map_err(|e| format!("Failed to open {:?}: {}", dir, e))
+}
+
+#[tauri::command]
+pub fn get_backend_log_tail<R: Runtime>(app: AppHandle<R>, lines: usize) -> Result<String, String> {
+    tail(&app, lines)
+}
+
+// Last `lines` lines of the backend log, capped at MAX_TAIL_LINES
+pub fn tail<R: Runtime>(app_handle: &AppHandle<R>, lines: usize) -> Result<String, String> {
+    let path =
+        log_path(app_handle).ok_or("Backend logging is unavailable: no app log directory")?;
+    if !path.exists() {
+        return Err("No backend log yet; it is created when the backend starts".to_string());
+    }
+
+    read_tail(&path, lines.min(MAX_TAIL_LINES))
+        .map_err(|e| format!("Failed to read backend log: {}", e))
+}
+
+// Last `lines` lines of the file, reading backwards from the end so a large
+// log is never loaded whole
+fn read_tail(path: &Path, lines: usize) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut pos = file.metadata()?.len();
+    let mut tail = Vec::new();
+
+    // One newline more than requested guarantees the first line is complete
+    while pos > 0 && tail.iter().filter(|&&b| b == b'\n').count() <= lines {
+        let len = TAIL_CHUNK_BYTES.min(pos);
+        pos -= len;
+
+        let mut chunk = vec![0; len as usize];
+        file.seek(SeekFrom::Start(pos))?;
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&tail);
+        tail = chunk;
+    }
+
+    let text = String::from_utf8_lossy(&tail);
+    let all: Vec<&str> = text.lines().collect();
+    Ok(all[all.len().saturating_sub(lines)..].join("\n"))
+}
+
+// Take the child's piped stdout/stderr and copy every line into backend.log.
+// The reader threads finish on their own once the child closes its pipes.
+// Both share one writer, so rotation never happens under a line being appended.
+pub fn capture<R: Runtime>(app_handle: &AppHandle<R>, process: &mut Child) {
+    let log = match open_log(app_handle) {
+        Ok(log) => Some(Arc::new(Mutex::new(log))),
+        Err(e) => {
+            // Keep draining the pipes anyway so the backend never blocks on a full buffer
+            eprintln!("Failed to open backend log: {}", e);
+            None
+        }
+    };
+
+    let sink = crate::settings::load(app_handle)
+        .stream_backend_log
+        .then(|| spawn_streamer(app_handle.clone()));
+
+    if let Some(stdout) = process.stdout.take() {
+        let output = Output {
+            log: log.clone(),
+            sink: sink.clone(),
+            echo: |line| println!("{}", line),
+        };
+        spawn_reader("stdout", stdout, output);
+    }
+    if let Some(stderr) = process.stderr.take() {
+        let output = Output {
+            log,
+            sink,
+            echo: |line| eprintln!("{}", line),
+        };
+        spawn_reader("stderr", stderr, output);
+    }
+}
+
+// Where a reader thread sends each line
+struct Output {
+    log: Option<SharedLog>,
+    sink: Option<Sender<LogLine>>,
+    echo: fn(&str),
+}
+
+// Forward lines from the reader threads to the frontend, grouping bursts so
+// a chatty backend can't flood the webview. Ends once both readers are done.
+fn spawn_streamer<R: Runtime>(app_handle: AppHandle<R>) -> Sender<LogLine> {
+    let (sender, receiver) = mpsc::channel();
+
+    let result = std::thread::Builder::new()
+        .name("backend-log-stream".to_string())
+        .spawn(move || {
+            while let Some(batch) = next_batch(&receiver) {
+                let _ = app_handle.emit(LOG_EVENT, batch);
+            }
+        });
+    if let Err(e) = result {
+        eprintln!("Failed to start backend log streaming: {}", e);
+    }
+
+    sender
+}
+
+// Wait for a line, then collect whatever else arrives within the batch window.
+// None once every sender is gone and nothing is left.
+fn next_batch(receiver: &Receiver<LogLine>) -> Option<Vec<LogLine>> {
+    let mut batch = vec![receiver.recv().ok()?];
+    let deadline = Instant::now() + STREAM_BATCH_WINDOW;
+
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let Ok(line) = receiver.recv_timeout(remaining) else {
+            break;
+        };
+        batch.push(line);
+    }
+
+    Some(batch)
+}
+
+fn open_log<R: Runtime>(app_handle: &AppHandle<R>) -> io::Result<RotatingLog> {
+    let path = log_path(app_handle)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no app log directory"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let settings = crate::settings::load(app_handle);
+    RotatingLog::open(
+        path,
+        // A hand-edited 0 would rotate before every line
+        settings.log_max_size_mb.max(1).saturating_mul(1024 * 1024),
+        settings.log_max_files,
+    )
+}
+
+fn spawn_reader<T: Read + Send + 'static>(name: &'static str, stream: T, output: Output) {
+    let result = std::thread::Builder::new()
+        .name(format!("backend-{}", name))
+        .spawn(move || {
+            let mut reader = BufReader::new(stream);
+            let mut buf = Vec::new();
+
+            loop {
+                buf.clear();
+                // EOF means the child closed the pipe
+                match reader.read_until(b'\n', &mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {}
+                }
+
+                let line = String::from_utf8_lossy(&buf);
+                let line = line.trim_end();
+
+                if cfg!(debug_assertions) {
+                    (output.echo)(line);
+                }
+
+                if let Some(log) = &output.log {
+                    if let Err(e) = log.lock().unwrap().write_line(line) {
+                        eprintln!("Failed to write backend log: {}", e);
+                    }
+                }
+
+                if let Some(sink) = &output.sink {
+                    let _ = sink.send(LogLine {
+                        stream: name,
+                        line: line.to_string(),
+                    });
+                }
+            }
+        });
+
+    if let Err(e) = result {
+        eprintln!("Failed to start backend {} reader: {}", name, e);
+    }
+}
+
+struct RotatingLog {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    // Rotate once backend.log passes this size, keeping this many old files
+    max_bytes: u64,
+    max_files: usize,
+}
+
+impl RotatingLog {
+    fn open(path: PathBuf, max_bytes: u64, max_files: usize) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+
+        Ok(Self {
+            path,
+            file,
+            size,
+            max_bytes,
+            max_files,
+        })
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        if self.size >= self.max_bytes {
+            self.rotate()?;
+        }
+
+        writeln!(self.file, "{}", line)?;
+        self.size += line.len() as u64 + 1;
+
+        Ok(())
+    }
+
+    // backend.log -> backend.1.log -> ... -> backend.N.log, dropping the oldest
+    fn rotate(&mut self) -> io::Result<()> {
+        // Also clears out files left over from a higher limit
+        let mut stale = self.max_files.max(1);
+        while rotated_path(&self.path, stale).exists() {
+            fs::remove_file(rotated_path(&self.path, stale))?;
+            stale += 1;
+        }
+
+        for index in (1..self.max_files).rev() {
+            let from = rotated_path(&self.path, index);
+            if from.exists() {
+                fs::rename(&from, rotated_path(&self.path, index + 1))?;
+            }
+        }
+        if self.max_files > 0 {
+            fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        }
+
+        self.file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.size = 0;
+
+        Ok(())
+    }
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    path.with_file_name(format!("{}.{}.log", LOG_FILE_STEM, index))
+}
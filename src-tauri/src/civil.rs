@@ -0,0 +1,19 @@
+// Calendar dates without a date crate. build.rs includes this file by path,
+// so it must not depend on the rest of the crate.
+
+// UTC date of a time `secs` seconds after the Unix epoch, as (year, month, day),
+// using the days-to-civil conversion from
+// http://howardhinnant.github.io/date_algorithms.html
+pub fn date_from_unix(secs: u64) -> (i64, u32, u32) {
+    let z = (secs / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (year, month as u32, day as u32)
+}
@@ -0,0 +1,124 @@
+use std::sync::OnceLock;
+
+// Options that configure the backend, as `--flag value`, and the environment
+// variable each one is passed to the backend as
+const ENV_FLAGS: &[(&str, &str)] = &[
+    ("--model-dir", "MODEL_DIR"),
+    ("--model", "DEFAULT_MODEL"),
+    ("--ollama-url", "OLLAMA_BASE_URL"),
+    ("--whisper-model", "WHISPER_MODEL_SIZE"),
+];
+
+// Backend settings from the command line, which take precedence over the settings file
+#[derive(Debug, Clone, Default)]
+pub struct CliArgs {
+    pub env: Vec<(String, String)>,
+    pub port: Option<u16>,
+    // Everything after `--`, handed to uvicorn as is
+    pub uvicorn_args: Vec<String>,
+}
+
+static ARGS: OnceLock<CliArgs> = OnceLock::new();
+
+// Parse the app's own command line, once, so unknown arguments are only
+// warned about at startup
+pub fn args() -> &'static CliArgs {
+    ARGS.get_or_init(|| parse(std::env::args().skip(1)))
+}
+
+fn parse(raw: impl Iterator<Item = String>) -> CliArgs {
+    let mut raw = raw.peekable();
+    let mut args = CliArgs::default();
+
+    while let Some(arg) = raw.next() {
+        // Also accept --flag=value
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                (flag.to_string(), Some(value.to_string()))
+            }
+            _ => (arg.clone(), None),
+        };
+
+        if flag == "--" {
+            args.uvicorn_args.extend(raw.by_ref());
+            break;
+        }
+
+        let env = ENV_FLAGS
+            .iter()
+            .find(|(name, _)| *name == flag)
+            .map(|(_, env)| *env);
+        if env.is_none() && flag != "--backend-port" {
+            // Deep links arrive as arguments on Windows and Linux, and macOS
+            // adds a process serial number when launched from Finder
+            if !arg.starts_with("renai:") && !arg.starts_with("-psn_") {
+                eprintln!("Warning: Ignoring unknown argument {:?}", arg);
+            }
+            continue;
+        }
+
+        // A flag where the value should be means this one was left without it
+        let Some(value) = inline.or_else(|| raw.next_if(|next| !next.starts_with("--"))) else {
+            eprintln!("Warning: Ignoring {} without a value", flag);
+            continue;
+        };
+
+        match env {
+            Some(env) => args.env.push((env.to_string(), value)),
+            None => match value.parse() {
+                Ok(port) => args.port = Some(port),
+                Err(_) => eprintln!(
+                    "Warning: Ignoring --backend-port {:?}, not a port number",
+                    value
+                ),
+            },
+        }
+    }
+
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> CliArgs {
+        parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn flags_take_values_separately_or_inline() {
+        let args = parse_args(&[
+            "--model",
+            "llama2",
+            "--ollama-url=http://gpu-box:11434",
+            "--backend-port",
+            "9000",
+            "--",
+            "--timeout-keep-alive",
+            "5",
+        ]);
+        assert_eq!(
+            args.env,
+            [
+                ("DEFAULT_MODEL".to_string(), "llama2".to_string()),
+                (
+                    "OLLAMA_BASE_URL".to_string(),
+                    "http://gpu-box:11434".to_string()
+                ),
+            ]
+        );
+        assert_eq!(args.port, Some(9000));
+        assert_eq!(args.uvicorn_args, ["--timeout-keep-alive", "5"]);
+    }
+
+    #[test]
+    fn a_flag_is_never_taken_as_a_value() {
+        let args = parse_args(&["--model-dir", "--backend-port", "9000", "--model"]);
+        assert!(args.env.is_empty());
+        assert_eq!(args.port, Some(9000));
+
+        let args = parse_args(&["--backend-port", "eighty", "renai://chat", "-psn_0_1234"]);
+        assert_eq!(args.port, None);
+    }
+}
@@ -0,0 +1,22 @@
+use tauri::{AppHandle, Manager, Runtime, WebviewUrl, WebviewWindowBuilder};
+
+pub const CONSOLE_LABEL: &str = "console";
+
+// Open the backend console, or bring it forward if it is already open. It
+// shows the live backend-log stream, so it is only useful while streaming is on.
+pub fn open<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
+    if let Some(window) = app.get_webview_window(CONSOLE_LABEL) {
+        window.unminimize()?;
+        window.show()?;
+        return window.set_focus();
+    }
+
+    WebviewWindowBuilder::new(app, CONSOLE_LABEL, WebviewUrl::App("console.html".into()))
+        .title("RenAI Backend Console")
+        .inner_size(760.0, 480.0)
+        .min_inner_size(400.0, 240.0)
+        .center()
+        .build()?;
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Runtime, Url};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+const SCHEME: &str = "renai";
+
+// Sent to the frontend for every renai:// link that is opened
+const DEEP_LINK_EVENT: &str = "deep-link";
+
+// Links that arrive before the main window is revealed wait here; None once
+// the window is up and links can be delivered straight away
+static PENDING_LINKS: Mutex<Option<Vec<DeepLinkPayload>>> = Mutex::new(Some(Vec::new()));
+
+#[derive(Debug, Clone, Serialize)]
+struct DeepLinkPayload {
+    url: String,
+    // `open` for renai://open?project=foo
+    action: String,
+    params: HashMap<String, String>,
+}
+
+impl From<&Url> for DeepLinkPayload {
+    fn from(url: &Url) -> Self {
+        Self {
+            url: url.to_string(),
+            action: url.host_str().unwrap_or_default().to_string(),
+            params: url.query_pairs().into_owned().collect(),
+        }
+    }
+}
+
+// Start listening for renai:// links, including the one the app was launched with.
+// On Windows and Linux a link opened while RenAI is running starts a second
+// instance instead, whose arguments reach handle_args.
+pub fn init<R: Runtime>(app_handle: &AppHandle<R>) {
+    let deep_link = app_handle.deep_link();
+
+    // Installers register the scheme; a dev build has to do it itself
+    #[cfg(all(debug_assertions, any(windows, target_os = "linux")))]
+    if let Err(e) = deep_link.register_all() {
+        eprintln!("Warning: Failed to register renai:// links: {}", e);
+    }
+
+    let handle = app_handle.clone();
+    deep_link.on_open_url(move |event| handle_urls(&handle, event.urls()));
+
+    if let Ok(Some(urls)) = deep_link.get_current() {
+        handle_urls(app_handle, urls);
+    }
+}
+
+// Open the renai:// links among a second launch's arguments, which the
+// single-instance plugin hands to this instance before the other one exits
+pub fn handle_args<R: Runtime>(app_handle: &AppHandle<R>, args: &[String]) {
+    let urls: Vec<Url> = args
+        .iter()
+        .filter_map(|arg| Url::parse(arg).ok())
+        .filter(|url| url.scheme() == SCHEME)
+        .collect();
+
+    if !urls.is_empty() {
+        handle_urls(app_handle, urls);
+    }
+}
+
+fn handle_urls<R: Runtime>(app_handle: &AppHandle<R>, urls: Vec<Url>) {
+    let mut pending = PENDING_LINKS.lock().unwrap();
+
+    for url in &urls {
+        println!("Opening deep link {}", url);
+        let payload = DeepLinkPayload::from(url);
+        match pending.as_mut() {
+            Some(queue) => queue.push(payload),
+            None => {
+                let _ = app_handle.emit(DEEP_LINK_EVENT, payload);
+            }
+        }
+    }
+
+    if pending.is_none() {
+        crate::tray::show_main_window(app_handle);
+    }
+}
+
+// Deliver links queued during startup; called once the main window is shown
+pub fn flush<R: Runtime>(app_handle: &AppHandle<R>) {
+    let Some(queue) = PENDING_LINKS.lock().unwrap().take() else {
+        return;
+    };
+
+    for payload in queue {
+        let _ = app_handle.emit(DEEP_LINK_EVENT, payload);
+    }
+}
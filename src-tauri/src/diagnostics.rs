@@ -0,0 +1,211 @@
+use serde_json::Value;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use sysinfo::System;
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_opener::OpenerExt;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, DateTime, ZipWriter};
+
+use crate::backend::{self, BackendManager};
+
+const BUNDLE_PREFIX: &str = "renai-diagnostics";
+
+// UTC date and time, as (year, month, day, hour, minute, second)
+type CivilTime = (u16, u8, u8, u8, u8, u8);
+
+// Zip up the logs, redacted settings and system details for a bug report,
+// into a timestamped archive in the Downloads folder
+pub fn create_bundle<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .download_dir()
+        .map_err(|e| format!("No Downloads directory: {}", e))?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let now = utc_now();
+    let (year, month, day, hour, minute, second) = now;
+    let path = dir.join(format!(
+        "{}-{:04}{:02}{:02}-{:02}{:02}{:02}.zip",
+        BUNDLE_PREFIX, year, month, day, hour, minute, second
+    ));
+
+    write_bundle(app, &path, now)
+        .map_err(|e| format!("Failed to write diagnostics to {:?}: {}", path, e))?;
+    println!("✓ Diagnostics saved to {:?}", path);
+    Ok(path)
+}
+
+// Create the bundle and show it in the file manager, for the tray
+pub fn export<R: Runtime>(app: &AppHandle<R>) {
+    let result = create_bundle(app).and_then(|path| {
+        app.opener()
+            .reveal_item_in_dir(&path)
+            .map_err(|e| format!("Failed to reveal {:?}: {}", path, e))
+    });
+    if let Err(e) = result {
+        eprintln!("{}", e);
+    }
+}
+
+fn write_bundle<R: Runtime>(
+    app: &AppHandle<R>,
+    path: &Path,
+    now: CivilTime,
+) -> zip::result::ZipResult<()> {
+    let (year, month, day, hour, minute, second) = now;
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .last_modified_time(
+            DateTime::from_date_and_time(year, month, day, hour, minute, second)
+                .unwrap_or_default(),
+        );
+    let mut zip = ZipWriter::new(File::create(path)?);
+
+    zip.start_file("system.txt", options)?;
+    zip.write_all(system_info(app).as_bytes())?;
+
+    zip.start_file("settings.json", options)?;
+    zip.write_all(redacted_settings(app).as_bytes())?;
+
+    // The app log and backend.log, rotated files included, share the log dir
+    let logs = app
+        .path()
+        .app_log_dir()
+        .ok()
+        .and_then(|dir| fs::read_dir(dir).ok());
+    for entry in logs.into_iter().flatten().flatten() {
+        let log_path = entry.path();
+        if !log_path.is_file() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().into_owned();
+        zip.start_file(format!("logs/{}", name), options)?;
+        io::copy(&mut File::open(&log_path)?, &mut zip)?;
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+fn system_info<R: Runtime>(app: &AppHandle<R>) -> String {
+    let manager = app.state::<BackendManager>();
+    let last_exit = manager
+        .last_exit()
+        .map(|exit| exit.to_string())
+        .unwrap_or_else(|| "none".to_string());
+
+    format!(
+        "RenAI {}\n\
+         Build {} ({})\n\
+         OS: {} ({} {})\n\
+         Kernel: {}\n\
+         Backend status: {:?}\n\
+         Backend version: {}\n\
+         Backend restarts: {}\n\
+         Last backend exit: {}\n",
+        app.package_info().version,
+        env!("RENAI_GIT_COMMIT"),
+        env!("RENAI_BUILD_DATE"),
+        System::long_os_version().unwrap_or_else(|| "unknown".to_string()),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        System::kernel_version().unwrap_or_else(|| "unknown".to_string()),
+        manager.status(),
+        backend::backend_version(app).unwrap_or_else(|| "unavailable".to_string()),
+        manager.restart_count(),
+        last_exit,
+    )
+}
+
+// settings.json as the app sees it, with the values of secret-looking backend
+// environment variables hidden
+fn redacted_settings<R: Runtime>(app: &AppHandle<R>) -> String {
+    let mut settings = serde_json::to_value(crate::settings::load(app)).unwrap_or_default();
+    redact(&mut settings);
+    serde_json::to_string_pretty(&settings).unwrap_or_default()
+}
+
+// Hide secrets in every `env` map, i.e. the default backend's and each profile's
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                match value {
+                    Value::Object(vars) if key == "env" => {
+                        for (name, value) in vars.iter_mut() {
+                            if backend::is_secret_env(name) {
+                                *value = Value::String("****".to_string());
+                            }
+                        }
+                    }
+                    _ => redact(value),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+// The current UTC time
+fn utc_now() -> CivilTime {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+
+    let (year, month, day) = crate::civil::date_from_unix(secs);
+    let time = secs % 86_400;
+    (
+        year as u16,
+        month as u8,
+        day as u8,
+        (time / 3600) as u8,
+        (time % 3600 / 60) as u8,
+        (time % 60) as u8,
+    )
+}
+
+#[tauri::command]
+pub async fn create_support_bundle<R: Runtime>(app: AppHandle<R>) -> Result<PathBuf, String> {
+    // Asks the backend for its version and compresses the logs, so keep it
+    // off the async workers
+    tauri::async_runtime::spawn_blocking(move || create_bundle(&app))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn redact_hides_secrets_in_every_env_map() {
+        let mut settings = json!({
+            "backend": {
+                "host": "127.0.0.1",
+                "env": { "OPENAI_API_KEY": "sk-123", "MODEL": "llama2" }
+            },
+            "profiles": [
+                { "name": "work", "backend": { "env": { "hf_token": "hf-456" } } }
+            ],
+            // Only names inside an env map are looked at
+            "apiKeyHint": "left as is"
+        });
+
+        redact(&mut settings);
+
+        assert_eq!(settings["backend"]["env"]["OPENAI_API_KEY"], "****");
+        assert_eq!(settings["backend"]["env"]["MODEL"], "llama2");
+        assert_eq!(settings["backend"]["host"], "127.0.0.1");
+        assert_eq!(
+            settings["profiles"][0]["backend"]["env"]["hf_token"],
+            "****"
+        );
+        assert_eq!(settings["apiKeyHint"], "left as is");
+    }
+}
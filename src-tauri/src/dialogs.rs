@@ -0,0 +1,208 @@
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_dialog::{
+    DialogExt, MessageDialogButtons, MessageDialogKind, MessageDialogResult,
+};
+use tauri_plugin_opener::OpenerExt;
+
+pub const PYTHON_DOWNLOAD_URL: &str = "https://www.python.org/downloads/";
+
+const RETRY_LABEL: &str = "Retry";
+const OPEN_LOGS_LABEL: &str = "Open Logs";
+const CONTINUE_LABEL: &str = "Continue";
+
+// What to do after the backend failed to start
+pub enum StartupChoice {
+    Retry,
+    OpenLogs,
+    Continue,
+}
+
+// Ask whether to quit while a task is still running; `on_quit` runs only if confirmed
+pub fn confirm_quit<R: Runtime, F: FnOnce() + Send + 'static>(app: &AppHandle<R>, on_quit: F) {
+    app.dialog()
+        .message("A task is still running — quit anyway?")
+        .title("Quit RenAI")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "Quit".to_string(),
+            "Cancel".to_string(),
+        ))
+        .show(move |quit| {
+            if quit {
+                on_quit();
+            }
+        });
+}
+
+// Version details for bug reports; `backend_version` is None if it couldn't be fetched
+pub fn show_about<R: Runtime>(app: &AppHandle<R>, backend_version: Option<String>) {
+    let message = format!(
+        "RenAI {}\nBackend {}\nBuild {} ({})",
+        app.package_info().version,
+        backend_version.as_deref().unwrap_or("version unavailable"),
+        env!("RENAI_GIT_COMMIT"),
+        env!("RENAI_BUILD_DATE"),
+    );
+
+    app.dialog()
+        .message(message)
+        .title("About RenAI")
+        .kind(MessageDialogKind::Info)
+        .buttons(MessageDialogButtons::Ok)
+        .show(|_| {});
+}
+
+// Offer to install a newer release; `on_install` runs only if accepted
+pub fn confirm_update<R: Runtime, F: FnOnce() + Send + 'static>(
+    app: &AppHandle<R>,
+    version: &str,
+    notes: Option<&str>,
+    on_install: F,
+) {
+    let mut message = format!(
+        "RenAI {} is available — you have {}.",
+        version,
+        app.package_info().version
+    );
+    if let Some(notes) = notes.filter(|notes| !notes.trim().is_empty()) {
+        message.push_str("\n\n");
+        message.push_str(notes.trim());
+    }
+    message.push_str("\n\nRenAI will restart once the update is installed.");
+
+    app.dialog()
+        .message(message)
+        .title("Update available")
+        .kind(MessageDialogKind::Info)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "Install and Restart".to_string(),
+            "Later".to_string(),
+        ))
+        .show(move |install| {
+            if install {
+                on_install();
+            }
+        });
+}
+
+pub fn show_up_to_date<R: Runtime>(app: &AppHandle<R>) {
+    app.dialog()
+        .message(format!(
+            "RenAI {} is the latest version.",
+            app.package_info().version
+        ))
+        .title("No updates")
+        .kind(MessageDialogKind::Info)
+        .buttons(MessageDialogButtons::Ok)
+        .show(|_| {});
+}
+
+pub fn show_update_failed<R: Runtime>(app: &AppHandle<R>, error: &str) {
+    app.dialog()
+        .message(format!("Couldn't update RenAI:\n{}", error))
+        .title("Update failed")
+        .kind(MessageDialogKind::Error)
+        .buttons(MessageDialogButtons::Ok)
+        .show(|_| {});
+}
+
+// Report a backend that failed to start; closing the dialog counts as Continue
+pub fn show_startup_failed<R: Runtime, F: FnOnce(StartupChoice) + Send + 'static>(
+    app: &AppHandle<R>,
+    error: &str,
+    on_choice: F,
+) {
+    app.dialog()
+        .message(format!("The RenAI backend failed to start:\n\n{}", error))
+        .title("Backend failed to start")
+        .kind(MessageDialogKind::Error)
+        .buttons(MessageDialogButtons::YesNoCancelCustom(
+            RETRY_LABEL.to_string(),
+            OPEN_LOGS_LABEL.to_string(),
+            CONTINUE_LABEL.to_string(),
+        ))
+        .show_with_result(move |result| {
+            // Some platforms report the button's role, others its label
+            let choice = match result {
+                MessageDialogResult::Yes => StartupChoice::Retry,
+                MessageDialogResult::No => StartupChoice::OpenLogs,
+                MessageDialogResult::Custom(label) if label == RETRY_LABEL => StartupChoice::Retry,
+                MessageDialogResult::Custom(label) if label == OPEN_LOGS_LABEL => {
+                    StartupChoice::OpenLogs
+                }
+                _ => StartupChoice::Continue,
+            };
+            on_choice(choice);
+        });
+}
+
+// Tell the user the watchdog stopped restarting a backend that kept crashing
+pub fn show_crash_loop<R: Runtime>(app: &AppHandle<R>, crashes: u32) {
+    let handle = app.clone();
+
+    app.dialog()
+        .message(format!(
+            "The RenAI backend keeps crashing ({} times in a row), so it won't be \
+             restarted automatically.\n\nCheck the backend log, then restart the \
+             backend from the tray menu.",
+            crashes
+        ))
+        .title("Backend keeps crashing")
+        .kind(MessageDialogKind::Error)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            OPEN_LOGS_LABEL.to_string(),
+            "Close".to_string(),
+        ))
+        .show(move |open_logs| {
+            if open_logs {
+                if let Err(e) = crate::backend_log::reveal(&handle) {
+                    eprintln!("{}", e);
+                }
+            }
+        });
+}
+
+// Explain that the backend needs Python and offer to open the download page
+pub fn show_python_missing<R: Runtime>(app: &AppHandle<R>) {
+    offer_python_download(
+        app,
+        "Python is required",
+        "RenAI runs its assistant backend with Python, but no Python interpreter \
+         was found on your PATH.\n\nInstall Python 3, then restart RenAI."
+            .to_string(),
+    );
+}
+
+// The interpreter found first, often an old system Python, can't run the backend
+pub fn show_python_too_old<R: Runtime>(app: &AppHandle<R>, found: &str, required: &str) {
+    offer_python_download(
+        app,
+        "Newer Python is required",
+        format!(
+            "RenAI's assistant backend needs Python {} or newer, but the interpreter \
+             it found is Python {}.\n\nInstall a newer Python, or choose one in \
+             Settings, then restart RenAI.",
+            required, found
+        ),
+    );
+}
+
+fn offer_python_download<R: Runtime>(app: &AppHandle<R>, title: &str, message: String) {
+    let handle = app.clone();
+
+    app.dialog()
+        .message(message)
+        .title(title)
+        .kind(MessageDialogKind::Error)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "Download Python".to_string(),
+            "Close".to_string(),
+        ))
+        .show(move |download| {
+            if download {
+                if let Err(e) = handle.opener().open_url(PYTHON_DOWNLOAD_URL, None::<&str>) {
+                    eprintln!("Failed to open Python download page: {}", e);
+                }
+            }
+        });
+}
@@ -0,0 +1,89 @@
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+use muda::{ContextMenu, Menu, MenuItem, PredefinedMenuItem};
+use objc2::runtime::{AnyClass, AnyObject, Imp, Sel};
+use objc2::{class, msg_send, sel};
+
+use crate::i18n::{self, Label, Language};
+
+// Signature of applicationDockMenu:, which returns the NSMenu to show
+type DockMenuFn = extern "C-unwind" fn(*mut AnyObject, Sel, *mut AnyObject) -> *mut c_void;
+
+// The NSMenu shown when the dock icon is right-clicked
+static DOCK_MENU: AtomicPtr<c_void> = AtomicPtr::new(std::ptr::null_mut());
+
+thread_local! {
+    // The menu's items and what they say, for relabel. muda items can't leave
+    // the main thread, which is the only one that touches them.
+    static DOCK_ITEMS: RefCell<Vec<(MenuItem, Label)>> = const { RefCell::new(Vec::new()) };
+}
+
+// Offer the main tray actions from the dock icon too. The items reuse the tray
+// menu's ids, so the tray's menu handler acts on them. The dock adds its own
+// Quit below them, which exits through RunEvent::Exit like the tray's.
+// Must be called on the main thread.
+pub fn create_dock_menu(language: Language) -> Result<(), String> {
+    let item =
+        |id: &str, label: Label| MenuItem::with_id(id, i18n::text(language, label), true, None);
+    let show = item("show", Label::ShowApp);
+    let hide = item("hide", Label::HideApp);
+    let restart = item("restart", Label::RestartBackend);
+    let menu = Menu::with_items(&[&show, &hide, &PredefinedMenuItem::separator(), &restart])
+        .map_err(|e| format!("Failed to create dock menu: {}", e))?;
+
+    DOCK_MENU.store(menu.ns_menu(), Ordering::SeqCst);
+    DOCK_ITEMS.with(|items| {
+        *items.borrow_mut() = vec![
+            (show, Label::ShowApp),
+            (hide, Label::HideApp),
+            (restart, Label::RestartBackend),
+        ];
+    });
+    // AppKit keeps asking for the menu for as long as the app runs
+    std::mem::forget(menu);
+
+    // AppKit asks the app delegate for the dock menu, and tao's delegate has no
+    // such method, so add one to its class
+    // SAFETY: the implementation matches the "@@:@" signature of
+    // applicationDockMenu:, and the delegate is set up before setup runs
+    unsafe {
+        let app: *mut AnyObject = msg_send![class!(NSApplication), sharedApplication];
+        let delegate: *mut AnyObject = msg_send![app, delegate];
+        let Some(delegate) = delegate.as_ref() else {
+            return Err("Failed to create dock menu: no app delegate".to_string());
+        };
+
+        let delegate_class = delegate.class() as *const AnyClass as *mut AnyClass;
+        let imp = std::mem::transmute::<DockMenuFn, Imp>(dock_menu);
+        let added = objc2::ffi::class_addMethod(
+            delegate_class,
+            sel!(applicationDockMenu:),
+            imp,
+            c"@@:@".as_ptr(),
+        );
+        if !added.as_bool() {
+            return Err("Failed to create dock menu: the app delegate already has one".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+// Show the dock menu in `language`. Must be called on the main thread.
+pub fn relabel(language: Language) {
+    DOCK_ITEMS.with(|items| {
+        for (item, label) in items.borrow().iter() {
+            item.set_text(i18n::text(language, *label));
+        }
+    });
+}
+
+extern "C-unwind" fn dock_menu(
+    _this: *mut AnyObject,
+    _cmd: Sel,
+    _sender: *mut AnyObject,
+) -> *mut c_void {
+    DOCK_MENU.load(Ordering::SeqCst)
+}
@@ -0,0 +1,117 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Runtime};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::backend::{self, BackendStatus};
+use crate::settings;
+
+// Emitted with the new state whenever it changes
+const HEALTH_CHANGED: &str = "backend-health";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HealthState {
+    // Answering its health check
+    Healthy,
+    // The process is alive but not answering, e.g. a deadlocked uvicorn
+    Unhealthy,
+    // No backend process at all
+    Down,
+}
+
+// A problem has to show up on this many polls in a row before the user is
+// notified, so a brief blip stays quiet
+const NOTIFY_AFTER_POLLS: u32 = 2;
+
+static HEALTH: Mutex<Option<HealthState>> = Mutex::new(None);
+
+// Last state seen by the health monitor, if it has run yet
+pub fn current() -> Option<HealthState> {
+    *HEALTH.lock().unwrap()
+}
+
+// Check the backend over HTTP every `health_poll_secs`, since a live process
+// doesn't mean it is still serving requests
+pub fn spawn_monitor<R: Runtime>(app_handle: AppHandle<R>) {
+    std::thread::spawn(move || {
+        let mut failing_polls = 0;
+        let mut notified = false;
+
+        loop {
+            let interval = settings::load(&app_handle).health_poll_secs.max(1);
+            std::thread::sleep(Duration::from_secs(interval));
+
+            let status = backend::backend_status(&app_handle);
+            let state = match status {
+                // Still coming up; the readiness check reports on that, and the
+                // old verdict no longer applies to the new process
+                BackendStatus::Starting => {
+                    *HEALTH.lock().unwrap() = None;
+                    failing_polls = 0;
+                    continue;
+                }
+                BackendStatus::Stopped
+                | BackendStatus::Paused
+                | BackendStatus::Suspended { .. }
+                | BackendStatus::Crashed => HealthState::Down,
+                BackendStatus::Running { .. } | BackendStatus::External { .. } => {
+                    if backend::probe_health(&backend::active_config(&app_handle)) {
+                        HealthState::Healthy
+                    } else {
+                        HealthState::Unhealthy
+                    }
+                }
+            };
+
+            // Stopped or paused means someone did it on purpose, which needs no alert
+            if state == HealthState::Unhealthy || matches!(status, BackendStatus::Crashed) {
+                failing_polls += 1;
+            } else {
+                failing_polls = 0;
+                notified = false;
+            }
+
+            if failing_polls >= NOTIFY_AFTER_POLLS && !notified {
+                notified = notify_unresponsive(&app_handle);
+            }
+
+            let previous = HEALTH.lock().unwrap().replace(state);
+            if previous == Some(state) {
+                continue;
+            }
+
+            if state == HealthState::Unhealthy {
+                eprintln!("Warning: Backend is running but not answering health checks");
+            }
+            crate::tray::update_tray_status(&app_handle, &status);
+            let _ = app_handle.emit(HEALTH_CHANGED, state);
+        }
+    });
+}
+
+// Tell a user who can't see the window that requests will fail. Desktop
+// notifications have no click handler; only on macOS does clicking one bring
+// the window back, through RunEvent::Reopen. Returns whether the user has now
+// been told.
+fn notify_unresponsive<R: Runtime>(app_handle: &AppHandle<R>) -> bool {
+    if crate::tray::main_window_visible(app_handle) {
+        // The window shows the problem itself; notify if it gets hidden later
+        return false;
+    }
+
+    let result = app_handle
+        .notification()
+        .builder()
+        .title("RenAI")
+        .body(crate::i18n::text(
+            crate::i18n::language(app_handle),
+            crate::i18n::Label::BackendUnresponsive,
+        ))
+        .show();
+    if let Err(e) = result {
+        eprintln!("Failed to show notification: {}", e);
+    }
+    true
+}
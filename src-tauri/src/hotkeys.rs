@@ -0,0 +1,158 @@
+use std::sync::Mutex;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+use crate::settings;
+
+// Currently registered shortcuts, so they can be swapped at runtime
+static TOGGLE_SHORTCUT: Mutex<Option<Shortcut>> = Mutex::new(None);
+static RESTART_SHORTCUT: Mutex<Option<Shortcut>> = Mutex::new(None);
+
+// Register `accelerator` as the show/hide shortcut, replacing the previous one.
+// If the new shortcut can't be registered the old one stays active.
+pub fn register_toggle_shortcut<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    accelerator: &str,
+) -> Result<(), String> {
+    let toggle = parse(accelerator)?;
+    let restart = *RESTART_SHORTCUT.lock().unwrap();
+    apply(app_handle, Some(toggle), restart)
+}
+
+// Register `accelerator` as the restart-backend shortcut; an empty one turns it off
+pub fn register_restart_shortcut<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    accelerator: &str,
+) -> Result<(), String> {
+    let restart = parse_optional(accelerator)?;
+    let toggle = *TOGGLE_SHORTCUT.lock().unwrap();
+    apply(app_handle, toggle, restart)
+}
+
+// Replace both shortcuts at once, so they can trade places. If either can't be
+// registered the old pair stays active.
+pub fn register_shortcuts<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    toggle: &str,
+    restart: &str,
+) -> Result<(), String> {
+    let toggle = parse(toggle)?;
+    let restart = parse_optional(restart)?;
+    apply(app_handle, Some(toggle), restart)
+}
+
+fn parse(accelerator: &str) -> Result<Shortcut, String> {
+    accelerator
+        .parse()
+        .map_err(|e| format!("Invalid shortcut {:?}: {}", accelerator, e))
+}
+
+// An empty accelerator means no shortcut
+fn parse_optional(accelerator: &str) -> Result<Option<Shortcut>, String> {
+    if accelerator.trim().is_empty() {
+        return Ok(None);
+    }
+    parse(accelerator).map(Some)
+}
+
+// Swap the registered shortcuts for `toggle` and `restart`. Both old ones are
+// released first, so a shortcut can move from one action to the other.
+fn apply<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    toggle: Option<Shortcut>,
+    restart: Option<Shortcut>,
+) -> Result<(), String> {
+    if let (Some(shortcut), true) = (toggle, toggle == restart) {
+        return Err(format!(
+            "Shortcut {} can't be both the show/hide and the restart backend shortcut",
+            shortcut
+        ));
+    }
+
+    let mut toggle_slot = TOGGLE_SHORTCUT.lock().unwrap();
+    let mut restart_slot = RESTART_SHORTCUT.lock().unwrap();
+    let (old_toggle, old_restart) = (*toggle_slot, *restart_slot);
+    if (old_toggle, old_restart) == (toggle, restart) {
+        return Ok(());
+    }
+
+    for old in [old_toggle, old_restart].into_iter().flatten() {
+        let _ = app_handle.global_shortcut().unregister(old);
+    }
+    if let Err(e) = bind_pair(app_handle, toggle, restart) {
+        if let Err(restore) = bind_pair(app_handle, old_toggle, old_restart) {
+            eprintln!(
+                "Warning: Failed to restore the previous shortcuts: {}",
+                restore
+            );
+        }
+        return Err(e);
+    }
+
+    *toggle_slot = toggle;
+    *restart_slot = restart;
+    Ok(())
+}
+
+// Register both shortcuts, or neither if one of them fails
+fn bind_pair<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    toggle: Option<Shortcut>,
+    restart: Option<Shortcut>,
+) -> Result<(), String> {
+    if let Some(toggle) = toggle {
+        bind(app_handle, toggle, crate::tray::toggle_main_window)?;
+    }
+    if let Some(restart) = restart {
+        if let Err(e) = bind(app_handle, restart, restart_backend) {
+            if let Some(toggle) = toggle {
+                let _ = app_handle.global_shortcut().unregister(toggle);
+            }
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+fn bind<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    shortcut: Shortcut,
+    on_press: fn(&AppHandle<R>),
+) -> Result<(), String> {
+    app_handle
+        .global_shortcut()
+        .on_shortcut(shortcut, move |app, _shortcut, event| {
+            if event.state == ShortcutState::Pressed {
+                on_press(app);
+            }
+        })
+        .map_err(|e| format!("Failed to register shortcut {}: {}", shortcut, e))
+}
+
+fn restart_backend<R: Runtime>(app_handle: &AppHandle<R>) {
+    // Restarting waits for readiness, so don't block the shortcut handler
+    let app = app_handle.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = crate::backend::restart_backend_server(&app) {
+            eprintln!("Failed to restart backend: {}", e);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn set_toggle_shortcut<R: Runtime>(app: AppHandle<R>, shortcut: String) -> Result<(), String> {
+    register_toggle_shortcut(&app, &shortcut)?;
+
+    let mut settings = settings::load(&app);
+    settings.toggle_shortcut = shortcut;
+    settings::save(&app, &settings)
+}
+
+#[tauri::command]
+pub fn set_restart_shortcut<R: Runtime>(app: AppHandle<R>, shortcut: String) -> Result<(), String> {
+    register_restart_shortcut(&app, &shortcut)?;
+
+    let mut settings = settings::load(&app);
+    settings.restart_shortcut = shortcut;
+    settings::save(&app, &settings)
+}
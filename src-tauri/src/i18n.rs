@@ -0,0 +1,265 @@
+use std::sync::OnceLock;
+use tauri::{AppHandle, Runtime};
+
+// Languages the tray and dock menus are translated into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    English,
+    German,
+    French,
+    Spanish,
+}
+
+impl Language {
+    // The language of a locale such as "de", "fr-CA" or "es_ES.UTF-8"
+    fn from_locale(locale: &str) -> Option<Self> {
+        let code = locale
+            .split(['-', '_', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match code.as_str() {
+            "en" => Some(Language::English),
+            "de" => Some(Language::German),
+            "fr" => Some(Language::French),
+            "es" => Some(Language::Spanish),
+            _ => None,
+        }
+    }
+}
+
+// Menu and notification texts, looked up with `text`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Label {
+    ShowApp,
+    Hide,
+    // Only in the macOS dock menu, where the item doesn't toggle
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    HideApp,
+    StartBackend,
+    RestartBackend,
+    PauseBackend,
+    ResumeBackend,
+    CopyBackendUrl,
+    OpenApiDocs,
+    BackendProfile,
+    DefaultProfile,
+    StartOnLogin,
+    Settings,
+    OpenLogs,
+    ShowConsole,
+    ExportDiagnostics,
+    CheckForUpdates,
+    About,
+    Relaunch,
+    Quit,
+    // Shown the first time the window is closed to the tray; the body's
+    // {quit} stands for the Quit item's label
+    StillRunning,
+    StillRunningBody,
+    // Confirms Copy Backend URL; {url} stands for what was copied
+    CopiedUrl,
+    BackendUnresponsive,
+}
+
+// The language to show menus in: Settings.locale if set, else the system's,
+// else English
+pub fn language<R: Runtime>(app: &AppHandle<R>) -> Language {
+    crate::settings::load(app)
+        .locale
+        .filter(|locale| !locale.is_empty())
+        .and_then(|locale| Language::from_locale(&locale))
+        .unwrap_or_else(system_language)
+}
+
+// Looked up once, since the OS only reports a change after a restart anyway
+fn system_language() -> Language {
+    static SYSTEM: OnceLock<Language> = OnceLock::new();
+    *SYSTEM.get_or_init(|| {
+        system_locale()
+            .and_then(|locale| Language::from_locale(&locale))
+            .unwrap_or(Language::English)
+    })
+}
+
+#[cfg(unix)]
+fn system_locale() -> Option<String> {
+    // Same precedence as setlocale; "C" and "POSIX" mean no preference
+    let from_env = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX");
+    if from_env.is_some() {
+        return from_env;
+    }
+
+    // Apps started from Finder or the Dock don't get LANG
+    if cfg!(target_os = "macos") {
+        return apple_locale();
+    }
+    None
+}
+
+// The region format chosen in System Settings, e.g. "en_GB"
+#[cfg(unix)]
+fn apple_locale() -> Option<String> {
+    let output = std::process::Command::new("defaults")
+        .args(["read", "-g", "AppleLocale"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(windows)]
+fn system_locale() -> Option<String> {
+    const LOCALE_NAME_MAX_LENGTH: usize = 85;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetUserDefaultLocaleName(locale_name: *mut u16, length: i32) -> i32;
+    }
+
+    let mut buffer = [0u16; LOCALE_NAME_MAX_LENGTH];
+    // SAFETY: the buffer is as long as we say, and the call writes at most that much
+    let written =
+        unsafe { GetUserDefaultLocaleName(buffer.as_mut_ptr(), buffer.len() as i32) } as usize;
+    // The count includes the terminating null; 0 means it failed
+    (written > 1).then(|| String::from_utf16_lossy(&buffer[..written - 1]))
+}
+
+pub fn text(language: Language, label: Label) -> &'static str {
+    use Label::*;
+    use Language::*;
+
+    match (label, language) {
+        (ShowApp, English) => "Show RenAI",
+        (ShowApp, German) => "RenAI anzeigen",
+        (ShowApp, French) => "Afficher RenAI",
+        (ShowApp, Spanish) => "Mostrar RenAI",
+
+        (Hide, English) => "Hide",
+        (Hide, German) => "Ausblenden",
+        (Hide, French) => "Masquer",
+        (Hide, Spanish) => "Ocultar",
+
+        (HideApp, English) => "Hide RenAI",
+        (HideApp, German) => "RenAI ausblenden",
+        (HideApp, French) => "Masquer RenAI",
+        (HideApp, Spanish) => "Ocultar RenAI",
+
+        (StartBackend, English) => "Start Backend",
+        (StartBackend, German) => "Backend starten",
+        (StartBackend, French) => "Démarrer le backend",
+        (StartBackend, Spanish) => "Iniciar el backend",
+
+        (RestartBackend, English) => "Restart Backend",
+        (RestartBackend, German) => "Backend neu starten",
+        (RestartBackend, French) => "Redémarrer le backend",
+        (RestartBackend, Spanish) => "Reiniciar el backend",
+
+        (PauseBackend, English) => "Pause Backend",
+        (PauseBackend, German) => "Backend pausieren",
+        (PauseBackend, French) => "Suspendre le backend",
+        (PauseBackend, Spanish) => "Pausar el backend",
+
+        (ResumeBackend, English) => "Resume Backend",
+        (ResumeBackend, German) => "Backend fortsetzen",
+        (ResumeBackend, French) => "Reprendre le backend",
+        (ResumeBackend, Spanish) => "Reanudar el backend",
+
+        (CopyBackendUrl, English) => "Copy Backend URL",
+        (CopyBackendUrl, German) => "Backend-URL kopieren",
+        (CopyBackendUrl, French) => "Copier l'URL du backend",
+        (CopyBackendUrl, Spanish) => "Copiar la URL del backend",
+
+        (OpenApiDocs, English) => "Open API Docs in Browser",
+        (OpenApiDocs, German) => "API-Dokumentation im Browser öffnen",
+        (OpenApiDocs, French) => "Ouvrir la documentation de l'API dans le navigateur",
+        (OpenApiDocs, Spanish) => "Abrir la documentación de la API en el navegador",
+
+        (BackendProfile, English) => "Backend Profile",
+        (BackendProfile, German) => "Backend-Profil",
+        (BackendProfile, French) => "Profil du backend",
+        (BackendProfile, Spanish) => "Perfil del backend",
+
+        (DefaultProfile, English) => "Default",
+        (DefaultProfile, German) => "Standard",
+        (DefaultProfile, French) => "Par défaut",
+        (DefaultProfile, Spanish) => "Predeterminado",
+
+        (StartOnLogin, English) => "Start on login",
+        (StartOnLogin, German) => "Bei der Anmeldung starten",
+        (StartOnLogin, French) => "Lancer à l'ouverture de session",
+        (StartOnLogin, Spanish) => "Iniciar al iniciar sesión",
+
+        (Settings, English) => "Settings…",
+        (Settings, German) => "Einstellungen…",
+        (Settings, French) => "Paramètres…",
+        (Settings, Spanish) => "Ajustes…",
+
+        (OpenLogs, English) => "Open Logs",
+        (OpenLogs, German) => "Protokolle öffnen",
+        (OpenLogs, French) => "Ouvrir les journaux",
+        (OpenLogs, Spanish) => "Abrir los registros",
+
+        (ShowConsole, English) => "Show Backend Console",
+        (ShowConsole, German) => "Backend-Konsole anzeigen",
+        (ShowConsole, French) => "Afficher la console du backend",
+        (ShowConsole, Spanish) => "Mostrar la consola del backend",
+
+        (ExportDiagnostics, English) => "Export Diagnostics",
+        (ExportDiagnostics, German) => "Diagnosedaten exportieren",
+        (ExportDiagnostics, French) => "Exporter les diagnostics",
+        (ExportDiagnostics, Spanish) => "Exportar diagnósticos",
+
+        (CheckForUpdates, English) => "Check for Updates",
+        (CheckForUpdates, German) => "Nach Updates suchen",
+        (CheckForUpdates, French) => "Rechercher des mises à jour",
+        (CheckForUpdates, Spanish) => "Buscar actualizaciones",
+
+        (About, English) => "About RenAI",
+        (About, German) => "Über RenAI",
+        (About, French) => "À propos de RenAI",
+        (About, Spanish) => "Acerca de RenAI",
+
+        (Relaunch, English) => "Relaunch RenAI",
+        (Relaunch, German) => "RenAI neu starten",
+        (Relaunch, French) => "Relancer RenAI",
+        (Relaunch, Spanish) => "Reiniciar RenAI",
+
+        (Quit, English) => "Quit RenAI",
+        (Quit, German) => "RenAI beenden",
+        (Quit, French) => "Quitter RenAI",
+        (Quit, Spanish) => "Salir de RenAI",
+
+        (StillRunning, English) => "RenAI is still running",
+        (StillRunning, German) => "RenAI läuft weiter",
+        (StillRunning, French) => "RenAI est toujours ouvert",
+        (StillRunning, Spanish) => "RenAI sigue abierto",
+
+        (StillRunningBody, English) => {
+            "It stays in the tray. Choose \"{quit}\" from the tray menu to exit completely."
+        }
+        (StillRunningBody, German) => {
+            "Es bleibt im Infobereich. Wähle „{quit}“ im Tray-Menü, um es ganz zu beenden."
+        }
+        (StillRunningBody, French) => {
+            "Il reste dans la barre d'état. Choisissez « {quit} » dans son menu pour le quitter."
+        }
+        (StillRunningBody, Spanish) => {
+            "Sigue en la bandeja del sistema. Elige «{quit}» en su menú para cerrarlo del todo."
+        }
+
+        (CopiedUrl, English) => "Copied {url}",
+        (CopiedUrl, German) => "{url} kopiert",
+        (CopiedUrl, French) => "{url} copiée",
+        (CopiedUrl, Spanish) => "{url} copiada",
+
+        (BackendUnresponsive, English) => "RenAI backend stopped responding",
+        (BackendUnresponsive, German) => "Das RenAI-Backend reagiert nicht mehr",
+        (BackendUnresponsive, French) => "Le backend de RenAI ne répond plus",
+        (BackendUnresponsive, Spanish) => "El backend de RenAI dejó de responder",
+    }
+}
@@ -0,0 +1,67 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Runtime};
+
+use crate::backend::{self, BackendStatus, PauseMode};
+use crate::settings;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+// When the main window was last hidden; None while it is visible
+static HIDDEN_SINCE: Mutex<Option<Instant>> = Mutex::new(None);
+
+// Set when the backend was paused for being idle, so showing the window
+// brings it back without overriding a pause the user asked for
+static IDLE_PAUSED: AtomicBool = AtomicBool::new(false);
+
+pub fn window_hidden() {
+    HIDDEN_SINCE.lock().unwrap().get_or_insert_with(Instant::now);
+}
+
+// Clear the idle timer and restart a backend that was stopped for being idle
+pub fn window_shown<R: Runtime>(app_handle: &AppHandle<R>) {
+    *HIDDEN_SINCE.lock().unwrap() = None;
+
+    if !IDLE_PAUSED.swap(false, Ordering::SeqCst) || !backend::is_paused(app_handle) {
+        return;
+    }
+
+    println!("Window shown, resuming the idle backend");
+    // Waits for the readiness probe, so the UI's requests land on a ready backend
+    let app_handle = app_handle.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = backend::resume_backend_server(&app_handle) {
+            eprintln!("Failed to resume backend: {}", e);
+        }
+    });
+}
+
+// Stop the backend once the window has been hidden for the configured time,
+// freeing its memory and GPU until the window is shown again
+pub fn spawn_monitor<R: Runtime>(app_handle: AppHandle<R>) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(CHECK_INTERVAL);
+
+        let settings = settings::load(&app_handle);
+        if !settings.idle_shutdown {
+            continue;
+        }
+
+        let threshold = Duration::from_secs(settings.idle_shutdown_mins.max(1) * 60);
+        let idle = HIDDEN_SINCE
+            .lock()
+            .unwrap()
+            .is_some_and(|since| since.elapsed() >= threshold);
+        // An external backend isn't ours to stop
+        let running = matches!(backend::backend_status(&app_handle), BackendStatus::Running { .. });
+        if !idle || !running {
+            continue;
+        }
+
+        println!("Window hidden for {:?}, stopping the idle backend", threshold);
+        // Suspending would keep the memory this is meant to free
+        backend::pause_backend_server(&app_handle, PauseMode::Stop);
+        IDLE_PAUSED.store(true, Ordering::SeqCst);
+    });
+}
@@ -0,0 +1,35 @@
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::backend::{self, BackendStatus};
+use crate::settings;
+
+// How often to look at the setting again while keep-alive is off
+const OFF_INTERVAL: Duration = Duration::from_secs(10);
+
+// Ping the backend every `keepalive_secs` so setups that shut down after a
+// while without requests stay warm. A paused or stopped backend is left alone.
+pub fn spawn_pinger<R: Runtime>(app_handle: AppHandle<R>) {
+    std::thread::spawn(move || loop {
+        let interval = settings::load(&app_handle)
+            .keepalive_secs
+            .filter(|&secs| secs > 0);
+        let Some(secs) = interval else {
+            std::thread::sleep(OFF_INTERVAL);
+            continue;
+        };
+        std::thread::sleep(Duration::from_secs(secs));
+
+        let status = backend::backend_status(&app_handle);
+        if !matches!(
+            status,
+            BackendStatus::Running { .. } | BackendStatus::External { .. }
+        ) {
+            continue;
+        }
+        // The health monitor reports a backend that stops answering
+        if !backend::probe_health(&backend::active_config(&app_handle)) {
+            eprintln!("Warning: Backend keep-alive ping got no answer");
+        }
+    });
+}
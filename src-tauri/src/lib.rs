@@ -1,51 +1,227 @@
+#[cfg(target_os = "macos")]
+mod app_menu;
+mod autostart;
 mod backend;
+mod backend_log;
+mod civil;
+mod cli;
+mod console_window;
+mod deep_link;
+mod diagnostics;
+mod dialogs;
+#[cfg(target_os = "macos")]
+mod dock;
+mod health;
+mod hotkeys;
+mod i18n;
+mod idle;
+mod keepalive;
+mod metrics;
+mod onboarding;
+mod settings;
+mod settings_window;
+mod splash;
 mod tray;
+mod updater;
+mod wake;
+mod window_state;
 
-use tauri::Manager;
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+
+#[derive(Clone, Serialize)]
+struct InstanceArgs {
+  args: Vec<String>,
+  cwd: String,
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+  // Check the command line before anything else gets to act on it
+  cli::args();
+
   tauri::Builder::default()
+    .manage(backend::BackendManager::default())
+    // Must be registered first so a second launch exits before doing anything else
+    .plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
+      // Bring the running instance forward, even from the tray, and hand it the
+      // new launch's arguments, including any renai:// link it was opened with
+      deep_link::handle_args(app, &args);
+      tray::show_main_window(app);
+      let _ = app.emit("second-instance", InstanceArgs { args, cwd });
+    }))
+    .plugin(tauri_plugin_deep_link::init())
+    .plugin(tauri_plugin_dialog::init())
+    .plugin(tauri_plugin_opener::init())
+    .plugin(tauri_plugin_clipboard_manager::init())
+    // Confirms tray actions such as Copy Backend URL, and tells a user whose
+    // window is hidden about backend trouble
+    .plugin(tauri_plugin_notification::init())
+    .plugin(tauri_plugin_autostart::init(
+      tauri_plugin_autostart::MacosLauncher::LaunchAgent,
+      None,
+    ))
+    .plugin(tauri_plugin_updater::Builder::new().build())
     .setup(|app| {
       if cfg!(debug_assertions) {
         app.handle().plugin(
           tauri_plugin_log::Builder::default()
-            .level(log::LevelFilter::Info)
+            .level(settings::load(app.handle()).log_level.filter())
             .build(),
         )?;
       }
 
-      // Start the FastAPI backend
-      if let Err(e) = backend::start_backend_server(&app.handle()) {
-        eprintln!("Failed to start backend: {}", e);
-        // Continue anyway - user might want to start backend manually
-      }
+      // renai:// links focus the app and are passed on to the frontend
+      deep_link::init(app.handle());
 
-      // Wait a moment for backend to start
-      std::thread::sleep(std::time::Duration::from_secs(2));
+      // Bring the backend back if it crashes
+      backend::spawn_watchdog(app.handle().clone());
+      // Notice when it stops answering even though the process is alive
+      health::spawn_monitor(app.handle().clone());
+      // Keep the tray tooltip's CPU and memory figures current
+      metrics::spawn_sampler(app.handle().clone());
+      // Free the backend's resources while the app sits in the tray
+      idle::spawn_monitor(app.handle().clone());
+      // Keep backends that stop themselves when idle warm, if asked to
+      keepalive::spawn_pinger(app.handle().clone());
+      // Restart a backend that didn't survive the machine sleeping
+      wake::spawn_monitor(app.handle().clone());
 
       // Create system tray
-      if let Err(e) = tray::create_tray(&app.handle()) {
+      if let Err(e) = tray::create_tray(app.handle()) {
         eprintln!("Failed to create system tray: {}", e);
       }
+      // Cmd+Q quits the way the tray's Quit does
+      #[cfg(target_os = "macos")]
+      if let Err(e) = app_menu::install(app.handle()) {
+        eprintln!("Failed to install app menu: {}", e);
+      }
+      // The same actions from the dock icon's menu
+      #[cfg(target_os = "macos")]
+      if let Err(e) = dock::create_dock_menu(i18n::language(app.handle())) {
+        eprintln!("{}", e);
+      }
+
+      // Global shortcut to show/hide the window - a taken shortcut isn't fatal
+      app
+        .handle()
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())?;
+      let settings = settings::load(app.handle());
+      if let Err(e) = hotkeys::register_toggle_shortcut(app.handle(), &settings.toggle_shortcut) {
+        eprintln!("Warning: {}", e);
+      }
+      // Another for restarting a wedged backend
+      if let Err(e) = hotkeys::register_restart_shortcut(app.handle(), &settings.restart_shortcut) {
+        eprintln!("Warning: {}", e);
+      }
+
+      // Dev builds aren't published, so only released ones look for updates
+      if settings.check_for_updates && !cfg!(debug_assertions) {
+        updater::check(app.handle(), false);
+      }
 
-      // Handle window close event - minimize to tray instead of quitting
       if let Some(window) = app.get_webview_window("main") {
         let app_handle = app.handle().clone();
-        window.on_window_event(move |event| {
-          if let tauri::WindowEvent::CloseRequested { api, .. } = event {
-            // Prevent default close behavior
-            api.prevent_close();
-            // Hide the window instead
+        window.on_window_event(move |event| match event {
+          // Handle window close event - minimize to tray instead of quitting,
+          // unless the user turned that off
+          tauri::WindowEvent::CloseRequested { api, .. } => {
             if let Some(win) = app_handle.get_webview_window("main") {
-              let _ = win.hide();
+              window_state::save(&win);
+            }
+            if !settings::load(&app_handle).close_to_tray {
+              // Let the window close and the app exit; the backend is stopped on Exit
+              return;
             }
+
+            // Prevent default close behavior
+            api.prevent_close();
+            // Hide the window instead, saying so the first time
+            tray::close_to_tray(&app_handle);
           }
+          tauri::WindowEvent::ThemeChanged(theme) => tray::set_theme(&app_handle, *theme),
+          _ => {}
         });
       }
 
+      // New users go through the setup wizard instead, which test-starts the
+      // backend itself and reveals the main window when it is done
+      if onboarding::is_first_run(app.handle()) {
+        match onboarding::open(app.handle()) {
+          Ok(()) => return Ok(()),
+          Err(e) => eprintln!("Failed to show onboarding window: {}", e),
+        }
+      }
+
+      // Show the splash while the FastAPI backend starts; the main window is
+      // revealed once it is actually serving. Starting hidden skips both and
+      // leaves the window to the tray and the hotkey, as does having left the
+      // window in the tray last time.
+      let start_hidden = settings.start_hidden || !window_state::was_visible(app.handle());
+      if start_hidden {
+        idle::window_hidden();
+      } else if let Err(e) = splash::show(app.handle()) {
+        eprintln!("Failed to show splash window: {}", e);
+      }
+      splash::launch_backend(app.handle().clone(), !start_hidden);
+
       Ok(())
     })
-    .run(tauri::generate_context!())
-    .expect("error while running tauri application");
+    .invoke_handler(tauri::generate_handler![
+      backend::get_backend_status,
+      backend::get_backend_state,
+      backend::get_backend_port,
+      backend::get_backend_uptime,
+      backend::get_backend_restart_count,
+      backend::get_backend_url,
+      backend::backend_running,
+      backend::backend_request,
+      backend::start_backend,
+      backend::restart_backend,
+      backend::pause_backend,
+      backend::set_backend_log_level,
+      backend::resume_backend,
+      backend_log::get_backend_log_tail,
+      diagnostics::create_support_bundle,
+      hotkeys::set_toggle_shortcut,
+      hotkeys::set_restart_shortcut,
+      metrics::get_backend_metrics,
+      onboarding::check_setup,
+      onboarding::pick_python_interpreter,
+      onboarding::open_python_download,
+      onboarding::test_backend_start,
+      onboarding::finish_onboarding,
+      settings::get_settings,
+      settings::update_settings,
+      settings::set_close_to_tray,
+      splash::get_startup_error,
+      splash::retry_startup,
+      splash::continue_without_backend,
+      tray::relaunch_app,
+      tray::set_task_in_progress
+    ])
+    .build(tauri::generate_context!())
+    .expect("error while building tauri application")
+    .run(|app_handle, event| {
+      // Every way out (tray quit, Cmd+Q, closing the last window, OS logout)
+      // ends here, so this is the one place the backend is cleaned up
+      match event {
+        tauri::RunEvent::Exit => {
+          // Geometry is saved when the window is hidden or closed rather than on
+          // every move, so catch a window that is still showing
+          if let Some(window) = app_handle.get_webview_window("main") {
+            window_state::save(&window);
+          }
+          window_state::shutting_down();
+          // Unsaved work in the backend would be lost with it
+          backend::save_backend_state(app_handle);
+          backend::stop_backend_server(app_handle);
+          tray::remove_tray(app_handle);
+        }
+        // Clicking the dock icon or one of our notifications re-activates the app
+        #[cfg(target_os = "macos")]
+        tauri::RunEvent::Reopen { .. } => tray::show_main_window(app_handle),
+        _ => {}
+      }
+    });
 }
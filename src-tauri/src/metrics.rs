@@ -0,0 +1,156 @@
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::Duration;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tauri::{AppHandle, Runtime};
+
+use crate::backend::{self, BackendStatus};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+
+// Latest sample of the backend we spawned; None while it isn't running
+static LATEST: Mutex<Option<BackendMetrics>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackendMetrics {
+    pub pid: u32,
+    // Relative to a single core, so several busy workers can exceed 100
+    pub cpu_percent: f32,
+    // Resident memory of the backend and its child processes
+    pub memory_bytes: u64,
+}
+
+impl BackendMetrics {
+    // Short summary for the tray tooltip, e.g. "3.2% CPU, 850 MB"
+    pub fn summary(&self) -> String {
+        format!(
+            "{:.1}% CPU, {} MB",
+            self.cpu_percent,
+            self.memory_bytes / (1024 * 1024)
+        )
+    }
+}
+
+pub fn current() -> Option<BackendMetrics> {
+    *LATEST.lock().unwrap()
+}
+
+// Periodically sample the backend's CPU and memory use and show it in the tray
+pub fn spawn_sampler<R: Runtime>(app_handle: AppHandle<R>) {
+    std::thread::spawn(move || {
+        // CPU usage is measured between refreshes, so the same System is kept around
+        let mut system = System::new();
+
+        loop {
+            std::thread::sleep(SAMPLE_INTERVAL);
+
+            // An external backend isn't ours to watch, and its pid is unknown
+            let sample = match backend::backend_status(&app_handle) {
+                BackendStatus::Running { pid, .. } => sample(&mut system, pid),
+                _ => None,
+            };
+
+            let previous = std::mem::replace(&mut *LATEST.lock().unwrap(), sample);
+            if previous.is_some() || sample.is_some() {
+                crate::tray::refresh_tooltip(&app_handle);
+            }
+        }
+    });
+}
+
+// Usage of `pid` and everything it spawned: uvicorn workers, or the Python
+// process a bundled executable unpacks and runs. None once the process is gone.
+fn sample(system: &mut System, pid: u32) -> Option<BackendMetrics> {
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing().with_cpu().with_memory(),
+    );
+
+    let root = Pid::from_u32(pid);
+    system.process(root)?;
+
+    let mut tree = HashSet::from([root]);
+    // Parents aren't listed before their children, so repeat until nothing is added
+    loop {
+        let before = tree.len();
+        for (child, process) in system.processes() {
+            // Linux lists each thread as a process under its owner, sharing
+            // the owner's memory, so counting them would add it again
+            if process.thread_kind().is_some() {
+                continue;
+            }
+            if process
+                .parent()
+                .is_some_and(|parent| tree.contains(&parent))
+            {
+                tree.insert(*child);
+            }
+        }
+        if tree.len() == before {
+            break;
+        }
+    }
+
+    let (cpu_percent, memory_bytes) = tree
+        .iter()
+        .filter_map(|pid| system.process(*pid))
+        .fold((0.0, 0), |(cpu, memory), process| {
+            (cpu + process.cpu_usage(), memory + process.memory())
+        });
+
+    Some(BackendMetrics {
+        pid,
+        cpu_percent,
+        memory_bytes,
+    })
+}
+
+#[tauri::command]
+pub fn get_backend_metrics() -> Option<BackendMetrics> {
+    current()
+}
+
+// Threads only show up as processes on Linux
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    // Resident memory of this process, from the kernel rather than sysinfo
+    fn own_rss() -> u64 {
+        let statm = std::fs::read_to_string("/proc/self/statm").unwrap();
+        let pages: u64 = statm.split_whitespace().nth(1).unwrap().parse().unwrap();
+        // SAFETY: sysconf has no memory-safety preconditions
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as u64;
+        pages * page_size
+    }
+
+    #[test]
+    fn threads_are_not_counted_as_children() {
+        // Threads that each would add the whole process's memory again; they
+        // stay alive until their sender is dropped
+        let (senders, threads): (Vec<_>, Vec<_>) = (0..4)
+            .map(|_| {
+                let (sender, receiver) = std::sync::mpsc::channel::<()>();
+                (sender, std::thread::spawn(move || receiver.recv()))
+            })
+            .unzip();
+
+        let mut system = System::new();
+        let metrics = sample(&mut system, std::process::id()).expect("no sample of itself");
+        let rss = own_rss();
+
+        assert!(
+            metrics.memory_bytes < rss * 3 / 2,
+            "sampled {} bytes, but the process only has {} resident",
+            metrics.memory_bytes,
+            rss
+        );
+        drop(senders);
+        for thread in threads {
+            let _ = thread.join();
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Manager, Runtime, WebviewUrl, WebviewWindowBuilder, WindowEvent};
+use tauri_plugin_dialog::DialogExt;
+use tauri_plugin_opener::OpenerExt;
+
+use crate::backend::{self, BackendConfig};
+
+pub const ONBOARDING_LABEL: &str = "onboarding";
+
+// Written to the app data dir when the wizard is finished; a launch without
+// it or saved settings is a first run
+const ONBOARDED_FLAG: &str = "onboarded";
+
+// Set by finish_onboarding, so closing the wizard any other way quits. Saved
+// settings can't tell, since the tray may write them while the wizard is open.
+static FINISHED: AtomicBool = AtomicBool::new(false);
+
+// What the wizard found; an error is set wherever a check failed
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetupCheck {
+    // The bundled backend brings its own Python, so the checks below are optional
+    bundled: bool,
+    // Interpreter that was checked; None for the platform default on PATH
+    python_path: Option<PathBuf>,
+    python_version: Option<String>,
+    python_error: Option<String>,
+    backend_dir: Option<PathBuf>,
+    backend_error: Option<String>,
+}
+
+fn flag_path<R: Runtime>(app: &AppHandle<R>) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join(ONBOARDED_FLAG))
+}
+
+// Installs from before the wizard existed have settings but no flag, and are
+// already set up
+pub fn is_first_run<R: Runtime>(app: &AppHandle<R>) -> bool {
+    flag_path(app).is_some_and(|path| !path.exists()) && !crate::settings::exists(app)
+}
+
+// Open the first-run wizard. Closing it before it is finished quits, since the
+// main window is still hidden and there would be nothing left to use.
+pub fn open<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
+    let window = WebviewWindowBuilder::new(
+        app,
+        ONBOARDING_LABEL,
+        WebviewUrl::App("onboarding.html".into()),
+    )
+    .title("Welcome to RenAI")
+    .inner_size(520.0, 560.0)
+    .resizable(false)
+    .center()
+    .build()?;
+
+    let app_handle = app.clone();
+    window.on_window_event(move |event| {
+        if matches!(event, WindowEvent::Destroyed) && !FINISHED.load(Ordering::SeqCst) {
+            app_handle.exit(0);
+        }
+    });
+
+    Ok(())
+}
+
+fn check<R: Runtime>(app: &AppHandle<R>, python_path: Option<PathBuf>) -> SetupCheck {
+    let config = BackendConfig {
+        python_path: python_path.clone(),
+        ..BackendConfig::load(app)
+    };
+
+    let (python_version, python_error) = match backend::python_version(&config) {
+        Ok(version) => (Some(version), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+    let (backend_dir, backend_error) = match backend::python_backend_dir(app) {
+        Ok(dir) => (Some(dir), None),
+        Err(e) => (None, Some(e.to_string())),
+    };
+
+    SetupCheck {
+        bundled: backend::has_bundled_backend(app),
+        python_path,
+        python_version,
+        python_error,
+        backend_dir,
+        backend_error,
+    }
+}
+
+// Look for a usable Python and the backend package, with `python_path` or
+// the platform default interpreter
+#[tauri::command]
+pub async fn check_setup<R: Runtime>(
+    app: AppHandle<R>,
+    python_path: Option<PathBuf>,
+) -> Result<SetupCheck, String> {
+    // Runs the interpreter, so keep it off the async workers
+    tauri::async_runtime::spawn_blocking(move || check(&app, python_path))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// Let the user browse for an interpreter, e.g. one in a virtualenv
+#[tauri::command]
+pub async fn pick_python_interpreter<R: Runtime>(app: AppHandle<R>) -> Option<PathBuf> {
+    tauri::async_runtime::spawn_blocking(move || {
+        app.dialog()
+            .file()
+            .set_title("Choose a Python interpreter")
+            .blocking_pick_file()
+    })
+    .await
+    .ok()
+    .flatten()
+    .and_then(|path| path.into_path().ok())
+}
+
+#[tauri::command]
+pub fn open_python_download<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    app.opener()
+        .open_url(crate::dialogs::PYTHON_DOWNLOAD_URL, None::<&str>)
+        .map_err(|e| format!("Failed to open Python download page: {}", e))
+}
+
+// Start the backend with the chosen interpreter, replacing one a previous
+// attempt left running. Nothing is saved until finish_onboarding, so only an
+// interpreter that worked ends up in the settings. Returns the port it serves on.
+#[tauri::command]
+pub async fn test_backend_start<R: Runtime>(
+    app: AppHandle<R>,
+    python_path: Option<PathBuf>,
+) -> Result<u16, String> {
+    let config = BackendConfig {
+        python_path,
+        ..BackendConfig::load(&app)
+    };
+
+    tauri::async_runtime::spawn_blocking(move || backend::start_trial_backend(&app, config))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+// Save the interpreter that passed the test and remember that setup is done,
+// then swap the wizard for the main window
+#[tauri::command]
+pub fn finish_onboarding<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    if let Some(config) = backend::take_trial_config(&app) {
+        let mut settings = crate::settings::load(&app);
+        settings.backend.python_path = config.python_path;
+        crate::settings::save(&app, &settings)?;
+    }
+
+    let path = flag_path(&app).ok_or("No app data directory")?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&path, "").map_err(|e| format!("Failed to save setup state: {}", e))?;
+    FINISHED.store(true, Ordering::SeqCst);
+
+    if let Some(window) = app.get_webview_window(ONBOARDING_LABEL) {
+        let _ = window.close();
+    }
+    crate::splash::finish(&app);
+    Ok(())
+}
@@ -0,0 +1,286 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::backend::BackendConfig;
+
+const SETTINGS_FILE: &str = "settings.json";
+
+// Backend overrides used to live in their own file; it seeds settings.json
+// the first time it is created
+const LEGACY_BACKEND_FILE: &str = "backend.json";
+
+pub const DEFAULT_TOGGLE_SHORTCUT: &str = "CommandOrControl+Shift+R";
+const DEFAULT_RESTART_SHORTCUT: &str = "CommandOrControl+Shift+B";
+const DEFAULT_HEALTH_POLL_SECS: u64 = 10;
+const DEFAULT_STARTUP_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_IDLE_SHUTDOWN_MINS: u64 = 30;
+const DEFAULT_LOG_MAX_SIZE_MB: u64 = 5;
+const DEFAULT_LOG_MAX_FILES: usize = 3;
+
+// Verbosity of both the app log and the backend
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub fn filter(self) -> log::LevelFilter {
+        match self {
+            LogLevel::Error => log::LevelFilter::Error,
+            LogLevel::Warn => log::LevelFilter::Warn,
+            LogLevel::Info => log::LevelFilter::Info,
+            LogLevel::Debug => log::LevelFilter::Debug,
+            LogLevel::Trace => log::LevelFilter::Trace,
+        }
+    }
+}
+
+// What a left click on the tray icon does. Linux trays always open the menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrayClick {
+    // Show the window if hidden, hide it if shown
+    Toggle,
+    // Always bring the window forward
+    Show,
+    // Open the menu, with a double click showing the window
+    Menu,
+}
+
+impl Default for TrayClick {
+    // A click opens the menu bar menu on macOS; elsewhere it usually opens the app
+    fn default() -> Self {
+        if cfg!(target_os = "macos") {
+            TrayClick::Menu
+        } else {
+            TrayClick::Toggle
+        }
+    }
+}
+
+// A named backend setup, e.g. a local dev backend or one using remote models
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackendProfile {
+    pub name: String,
+    #[serde(flatten)]
+    pub backend: BackendConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Settings {
+    // Global shortcut that shows/hides the main window
+    pub toggle_shortcut: String,
+    // Global shortcut that restarts the backend; empty to disable
+    pub restart_shortcut: String,
+    // Hide the main window to the tray on close instead of quitting
+    pub close_to_tray: bool,
+    // Ask before quitting while a response is still being generated
+    pub confirm_quit: bool,
+    // Launch RenAI when the user logs in
+    pub autostart: bool,
+    // Start in the tray without showing any window, e.g. when launched at login
+    pub start_hidden: bool,
+    pub tray_click: TrayClick,
+    // Language of the tray menu, e.g. "de"; the system language when unset
+    pub locale: Option<String>,
+    // Look for a new release in the background at startup
+    pub check_for_updates: bool,
+    // Seconds between HTTP health checks of the running backend
+    pub health_poll_secs: u64,
+    pub log_level: LogLevel,
+    // backend.log is rotated past this size, keeping this many older files
+    pub log_max_size_mb: u64,
+    pub log_max_files: usize,
+    // Send backend output to the frontend as it arrives, for a live console
+    pub stream_backend_log: bool,
+    // How long a newly spawned backend gets to answer its health check
+    pub startup_timeout_secs: u64,
+    // Stop the backend after the window has been hidden this long, and start
+    // it again when the window is shown
+    pub idle_shutdown: bool,
+    pub idle_shutdown_mins: u64,
+    // Pause by suspending the backend with SIGSTOP instead of stopping it, so
+    // its models stay loaded; Unix only, elsewhere pausing always stops it
+    pub suspend_on_pause: bool,
+    // Seconds between pings that keep an idle backend from shutting itself
+    // down; unset or 0 to turn them off
+    pub keepalive_secs: Option<u64>,
+    // Folder holding the backend/ package; found automatically when unset
+    pub backend_dir: Option<PathBuf>,
+    // Port, interpreter and environment for the FastAPI backend; changes
+    // apply the next time it starts
+    pub backend: BackendConfig,
+    // Alternative backends that can be switched to from the tray
+    pub profiles: Vec<BackendProfile>,
+    // Name of the profile in use; `backend` is used when unset or unknown
+    pub active_profile: Option<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            toggle_shortcut: DEFAULT_TOGGLE_SHORTCUT.to_string(),
+            restart_shortcut: DEFAULT_RESTART_SHORTCUT.to_string(),
+            close_to_tray: true,
+            confirm_quit: true,
+            autostart: false,
+            start_hidden: false,
+            tray_click: TrayClick::default(),
+            locale: None,
+            check_for_updates: true,
+            health_poll_secs: DEFAULT_HEALTH_POLL_SECS,
+            log_level: LogLevel::default(),
+            log_max_size_mb: DEFAULT_LOG_MAX_SIZE_MB,
+            log_max_files: DEFAULT_LOG_MAX_FILES,
+            stream_backend_log: true,
+            startup_timeout_secs: DEFAULT_STARTUP_TIMEOUT_SECS,
+            idle_shutdown: false,
+            idle_shutdown_mins: DEFAULT_IDLE_SHUTDOWN_MINS,
+            suspend_on_pause: false,
+            keepalive_secs: None,
+            backend_dir: None,
+            backend: BackendConfig::default(),
+            profiles: Vec::new(),
+            active_profile: None,
+        }
+    }
+}
+
+impl Settings {
+    // Config of the active profile, falling back to the default backend
+    pub fn active_backend(&self) -> &BackendConfig {
+        self.active_profile
+            .as_deref()
+            .and_then(|name| self.profiles.iter().find(|profile| profile.name == name))
+            .map(|profile| &profile.backend)
+            .unwrap_or(&self.backend)
+    }
+}
+
+fn config_path<R: Runtime>(app_handle: &AppHandle<R>, file: &str) -> Option<PathBuf> {
+    app_handle
+        .path()
+        .app_config_dir()
+        .ok()
+        .map(|dir| dir.join(file))
+}
+
+// Read settings.json, using defaults when it is missing or malformed. Fields
+// missing from older files fall back to their defaults.
+pub fn load<R: Runtime>(app_handle: &AppHandle<R>) -> Settings {
+    let Some(path) = config_path(app_handle, SETTINGS_FILE) else {
+        return Settings::default();
+    };
+
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return Settings {
+            backend: load_legacy_backend(app_handle).unwrap_or_default(),
+            ..Settings::default()
+        };
+    };
+
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        // Moved aside so the next save doesn't overwrite what the user had
+        let bad_path = path.with_extension("json.bad");
+        eprintln!(
+            "Warning: Ignoring malformed settings {:?}: {}; keeping it as {:?}",
+            path, e, bad_path
+        );
+        if let Err(e) = std::fs::rename(&path, &bad_path) {
+            eprintln!("Warning: Failed to move malformed settings aside: {}", e);
+        }
+        Settings::default()
+    })
+}
+
+fn load_legacy_backend<R: Runtime>(app_handle: &AppHandle<R>) -> Option<BackendConfig> {
+    let path = config_path(app_handle, LEGACY_BACKEND_FILE)?;
+    let contents = std::fs::read_to_string(&path).ok()?;
+
+    serde_json::from_str(&contents)
+        .map_err(|e| {
+            eprintln!(
+                "Warning: Ignoring malformed backend config {:?}: {}",
+                path, e
+            )
+        })
+        .ok()
+}
+
+// Whether settings.json was ever saved, which only a set-up install has
+pub fn exists<R: Runtime>(app_handle: &AppHandle<R>) -> bool {
+    config_path(app_handle, SETTINGS_FILE).is_some_and(|path| path.exists())
+}
+
+// Write settings.json via a temp file and rename, so a crash mid-write can't
+// leave a truncated file behind
+pub fn save<R: Runtime>(app_handle: &AppHandle<R>, settings: &Settings) -> Result<(), String> {
+    let path = config_path(app_handle, SETTINGS_FILE).ok_or("No app config directory")?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+
+    let contents = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, contents).map_err(|e| format!("Failed to save settings: {}", e))?;
+    std::fs::rename(&tmp_path, &path).map_err(|e| format!("Failed to save settings: {}", e))
+}
+
+#[tauri::command]
+pub fn get_settings<R: Runtime>(app: AppHandle<R>) -> Settings {
+    load(&app)
+}
+
+// Replace the stored settings, applying the ones that can change at runtime
+#[tauri::command]
+pub fn update_settings<R: Runtime>(
+    app: AppHandle<R>,
+    settings: Settings,
+) -> Result<Settings, String> {
+    if settings.log_max_size_mb == 0 {
+        return Err("The backend log size limit must be at least 1 MB".to_string());
+    }
+    // Fails without saving if a new shortcut can't be registered
+    crate::hotkeys::register_shortcuts(
+        &app,
+        &settings.toggle_shortcut,
+        &settings.restart_shortcut,
+    )?;
+    crate::autostart::apply(&app, settings.autostart)?;
+    crate::tray::set_autostart_checked(&app, settings.autostart);
+    crate::tray::refresh_profile_menu(&app, &settings);
+    crate::tray::apply_tray_click(&app, settings.tray_click);
+
+    let previous_level = load(&app).log_level;
+    save(&app, &settings)?;
+    // Labels follow the saved locale
+    crate::tray::relabel_menu(&app, &settings);
+
+    // Changing it may restart the backend, so don't block the caller
+    if settings.log_level != previous_level {
+        let app = app.clone();
+        let level = settings.log_level;
+        std::thread::spawn(move || {
+            if let Err(e) = crate::backend::apply_log_level(&app, level) {
+                eprintln!("Failed to apply log level: {}", e);
+            }
+        });
+    }
+    Ok(settings)
+}
+
+#[tauri::command]
+pub fn set_close_to_tray<R: Runtime>(app: AppHandle<R>, enabled: bool) -> Result<(), String> {
+    let mut settings = load(&app);
+    settings.close_to_tray = enabled;
+    save(&app, &settings)
+}
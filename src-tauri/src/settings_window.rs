@@ -0,0 +1,22 @@
+use tauri::{AppHandle, Manager, Runtime, WebviewUrl, WebviewWindowBuilder};
+
+pub const SETTINGS_LABEL: &str = "settings";
+
+// Open the settings window, or bring it forward if it is already open. It is
+// an ordinary window: closing it closes it, unlike the main window.
+pub fn open<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
+    if let Some(window) = app.get_webview_window(SETTINGS_LABEL) {
+        window.unminimize()?;
+        window.show()?;
+        return window.set_focus();
+    }
+
+    WebviewWindowBuilder::new(app, SETTINGS_LABEL, WebviewUrl::App("settings.html".into()))
+        .title("RenAI Settings")
+        .inner_size(480.0, 600.0)
+        .min_inner_size(400.0, 400.0)
+        .center()
+        .build()?;
+
+    Ok(())
+}
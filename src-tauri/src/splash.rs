@@ -0,0 +1,130 @@
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, Runtime, WebviewUrl, WebviewWindowBuilder};
+
+use crate::backend::{self, BackendError};
+use crate::dialogs::{self, StartupChoice};
+use crate::tray;
+
+pub const SPLASH_LABEL: &str = "splash";
+
+// Sent to the splash window when the backend couldn't be started
+const STARTUP_FAILED: &str = "backend-startup-failed";
+
+// Kept so a splash page that loads after the failure can still show it
+static STARTUP_ERROR: Mutex<Option<String>> = Mutex::new(None);
+
+// Show a small borderless window while the backend comes up
+pub fn show<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
+    WebviewWindowBuilder::new(app, SPLASH_LABEL, WebviewUrl::App("splash.html".into()))
+        .title("RenAI")
+        .inner_size(360.0, 220.0)
+        .resizable(false)
+        .decorations(false)
+        .skip_taskbar(true)
+        .always_on_top(true)
+        .center()
+        .build()?;
+
+    Ok(())
+}
+
+// Start the backend on an async task, then swap the splash for the main
+// window once it's ready, or leave the error on the splash for a retry.
+// Returns right away, so setup and the event loop never wait on the backend.
+// Without `reveal` the app stays in the tray unless the start fails.
+pub fn launch_backend<R: Runtime>(app: AppHandle<R>, reveal: bool) {
+    *STARTUP_ERROR.lock().unwrap() = None;
+
+    tauri::async_runtime::spawn(async move {
+        let done = move |app: &AppHandle<R>| {
+            if reveal {
+                finish(app);
+            } else {
+                crate::deep_link::flush(app);
+            }
+        };
+
+        // Spawning and the readiness probe block, so keep them off the async workers
+        let handle = app.clone();
+        let result = tauri::async_runtime::spawn_blocking(move || {
+            backend::start_backend_with_retries(&handle)
+        })
+        .await;
+        let result = match result {
+            Ok(result) => result,
+            Err(e) => {
+                eprintln!("Backend startup task failed: {}", e);
+                return;
+            }
+        };
+
+        let e = match result {
+            Ok(port) => {
+                println!("✓ Backend serving on port {}", port);
+                done(&app);
+                return;
+            }
+            Err(e) => e,
+        };
+        eprintln!("Failed to start backend: {}", e);
+
+        // Someone else's start got there first, which is as good as ours
+        if matches!(e.root_cause(), BackendError::AlreadyRunning) {
+            done(&app);
+            return;
+        }
+
+        // Leave the error on the splash with its retry button
+        let message = e.to_string();
+        *STARTUP_ERROR.lock().unwrap() = Some(message.clone());
+        let _ = app.emit_to(SPLASH_LABEL, STARTUP_FAILED, &message);
+
+        match e.root_cause() {
+            // Retrying is pointless until Python is installed, so explain what's missing
+            BackendError::PythonNotFound => dialogs::show_python_missing(&app),
+            BackendError::PythonTooOld { found, required } => {
+                dialogs::show_python_too_old(&app, found, required)
+            }
+            // Also reaches a silent start, which has no splash to show the error on
+            _ => {
+                let handle = app.clone();
+                dialogs::show_startup_failed(&app, &message, move |choice| match choice {
+                    StartupChoice::Retry => launch_backend(handle, reveal),
+                    StartupChoice::OpenLogs => {
+                        if let Err(e) = crate::backend_log::reveal(&handle) {
+                            eprintln!("{}", e);
+                        }
+                    }
+                    StartupChoice::Continue => done(&handle),
+                });
+            }
+        }
+    });
+}
+
+// Close the splash and reveal the main window
+pub fn finish<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(splash) = app.get_webview_window(SPLASH_LABEL) {
+        let _ = splash.close();
+    }
+    tray::show_main_window(app);
+
+    // The frontend is up now, so it can act on links that opened the app
+    crate::deep_link::flush(app);
+}
+
+#[tauri::command]
+pub fn get_startup_error() -> Option<String> {
+    STARTUP_ERROR.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn retry_startup<R: Runtime>(app: AppHandle<R>) {
+    launch_backend(app, true);
+}
+
+// Open the main window without a backend - it may be started manually
+#[tauri::command]
+pub fn continue_without_backend<R: Runtime>(app: AppHandle<R>) {
+    finish(&app);
+}
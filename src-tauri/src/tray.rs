@@ -1,67 +1,850 @@
 use tauri::{
-    menu::{Menu, MenuItem},
-    tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager, Runtime,
+    image::Image,
+    menu::{CheckMenuItem, Menu, MenuItem, Submenu},
+    tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent},
+    Manager, Runtime, Theme,
 };
 
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_opener::OpenerExt;
+
+use crate::backend::{BackendError, BackendStatus, PauseMode};
+use crate::i18n::{self, Label};
+use crate::settings::{self, Settings, TrayClick};
+
+pub const TRAY_ID: &str = "main-tray";
+
+// Status variants of the tray icon, with a colored dot in the corner. Each
+// comes as a dark glyph for light taskbars and a light glyph for dark ones.
+const RUNNING_ICON: [&[u8]; 2] = [
+    include_bytes!("../icons/tray/running-light.png"),
+    include_bytes!("../icons/tray/running-dark.png"),
+];
+const STARTING_ICON: [&[u8]; 2] = [
+    include_bytes!("../icons/tray/starting-light.png"),
+    include_bytes!("../icons/tray/starting-dark.png"),
+];
+const STOPPED_ICON: [&[u8]; 2] = [
+    include_bytes!("../icons/tray/stopped-light.png"),
+    include_bytes!("../icons/tray/stopped-dark.png"),
+];
+
+// Spinner frames cycled while the backend starts, the dot's bright arc
+// moving an eighth of a turn per frame
+const STARTING_FRAMES: [[&[u8]; 2]; 8] = [
+    [
+        include_bytes!("../icons/tray/starting-light-0.png"),
+        include_bytes!("../icons/tray/starting-dark-0.png"),
+    ],
+    [
+        include_bytes!("../icons/tray/starting-light-1.png"),
+        include_bytes!("../icons/tray/starting-dark-1.png"),
+    ],
+    [
+        include_bytes!("../icons/tray/starting-light-2.png"),
+        include_bytes!("../icons/tray/starting-dark-2.png"),
+    ],
+    [
+        include_bytes!("../icons/tray/starting-light-3.png"),
+        include_bytes!("../icons/tray/starting-dark-3.png"),
+    ],
+    [
+        include_bytes!("../icons/tray/starting-light-4.png"),
+        include_bytes!("../icons/tray/starting-dark-4.png"),
+    ],
+    [
+        include_bytes!("../icons/tray/starting-light-5.png"),
+        include_bytes!("../icons/tray/starting-dark-5.png"),
+    ],
+    [
+        include_bytes!("../icons/tray/starting-light-6.png"),
+        include_bytes!("../icons/tray/starting-dark-6.png"),
+    ],
+    [
+        include_bytes!("../icons/tray/starting-light-7.png"),
+        include_bytes!("../icons/tray/starting-dark-7.png"),
+    ],
+];
+const SPINNER_INTERVAL: Duration = Duration::from_millis(125);
+
+// Monochrome silhouette that macOS tints to suit light and dark menu bars
+const TEMPLATE_ICON: &[u8] = include_bytes!("../icons/tray/template.png");
+
+// The show/hide menu item, kept so its label can follow the window's visibility
+struct ToggleItem<R: Runtime>(MenuItem<R>);
+
+// The pause/resume item, whose label follows the backend status
+struct PauseItem<R: Runtime>(MenuItem<R>);
+
+// Shows the backend URL and status, disabled since there is nothing to click
+struct UrlItem<R: Runtime>(MenuItem<R>);
+
+// Opens the API docs, only enabled while a backend is serving
+struct DocsItem<R: Runtime>(MenuItem<R>);
+
+// Starts the backend by hand, only enabled while there is none
+struct StartItem<R: Runtime>(MenuItem<R>);
+
+// The start-on-login checkbox, updated when the setting changes elsewhere
+struct AutostartItem<R: Runtime>(CheckMenuItem<R>);
+
+// The backend profile submenu, rebuilt whenever the profiles change
+struct ProfileMenu<R: Runtime>(Submenu<R>);
+
+// Items with a fixed label, kept so they can be relabeled when the language changes
+struct LabeledItems<R: Runtime>(Vec<(MenuItem<R>, Label)>);
+
+// The tray icon once built, so running setup again updates it instead of
+// stacking a second icon. Emptied when the icon is removed on quit.
+struct TrayHandle<R: Runtime>(Mutex<Option<TrayIcon<R>>>);
+
+// Menu ids of profile entries; the default backend has an empty name
+const PROFILE_ID_PREFIX: &str = "profile:";
+
+// Set once the user has been told that closing the window left RenAI running
+static CLOSED_TO_TRAY_NOTICE: AtomicBool = AtomicBool::new(false);
+
+// Set by the frontend while a response is being generated
+static TASK_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+// Whether the system uses a dark theme, which decides the icon variant
+static DARK_THEME: AtomicBool = AtomicBool::new(false);
+
+// Id of the running spinner thread, if any; a thread whose id was replaced exits
+static SPINNER: Mutex<Option<u32>> = Mutex::new(None);
+static NEXT_SPINNER_ID: AtomicU32 = AtomicU32::new(0);
+
 pub fn create_tray<R: Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<()> {
+    // Already there, e.g. setup ran again during development
+    if tray(app).is_some() {
+        update_tray_status(app, &crate::backend::backend_status(app));
+        relabel_menu(app, &settings::load(app));
+        return Ok(());
+    }
+
+    // The main window exists (hidden) from the start, so it can report the theme
+    if let Some(theme) = app
+        .get_webview_window("main")
+        .and_then(|window| window.theme().ok())
+    {
+        DARK_THEME.store(theme == Theme::Dark, Ordering::SeqCst);
+    }
+
     // Create menu items
-    let show_i = MenuItem::with_id(app, "show", "Show RenAI", true, None::<&str>)?;
-    let hide_i = MenuItem::with_id(app, "hide", "Hide", true, None::<&str>)?;
-    let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    let language = i18n::language(app);
+    let item = |id: &str, label: Label| {
+        MenuItem::with_id(app, id, i18n::text(language, label), true, None::<&str>)
+    };
+    let toggle_i = MenuItem::with_id(app, "toggle", toggle_label(app), true, None::<&str>)?;
+    let start_i = MenuItem::with_id(
+        app,
+        "start",
+        i18n::text(language, Label::StartBackend),
+        can_start(&crate::backend::backend_status(app)),
+        None::<&str>,
+    )?;
+    let restart_i = item("restart", Label::RestartBackend)?;
+    let pause_i = MenuItem::with_id(app, "pause", pause_label(app), true, None::<&str>)?;
+    let url_i = MenuItem::with_id(
+        app,
+        "backend_url",
+        url_label(app, &crate::backend::backend_status(app)),
+        false,
+        None::<&str>,
+    )?;
+    let copy_url_i = item("copy_url", Label::CopyBackendUrl)?;
+    let docs_i = MenuItem::with_id(
+        app,
+        "open_docs",
+        i18n::text(language, Label::OpenApiDocs),
+        serving(&crate::backend::backend_status(app)),
+        None::<&str>,
+    )?;
+    let profiles_i = Submenu::with_id(
+        app,
+        "profiles",
+        i18n::text(language, Label::BackendProfile),
+        true,
+    )?;
+    let settings = settings::load(app);
+    fill_profile_menu(app, &profiles_i, &settings)?;
+    let autostart_i = CheckMenuItem::with_id(
+        app,
+        "autostart",
+        i18n::text(language, Label::StartOnLogin),
+        true,
+        crate::autostart::sync_setting(app),
+        None::<&str>,
+    )?;
+    let settings_i = item("settings", Label::Settings)?;
+    let open_logs_i = item("open_logs", Label::OpenLogs)?;
+    let console_i = item("console", Label::ShowConsole)?;
+    let diagnostics_i = item("diagnostics", Label::ExportDiagnostics)?;
+    let updates_i = item("check_updates", Label::CheckForUpdates)?;
+    let about_i = item("about", Label::About)?;
+    let relaunch_i = item("relaunch", Label::Relaunch)?;
+    // Quits outright, unlike closing the window with close to tray on
+    let quit_i = MenuItem::with_id(
+        app,
+        "quit",
+        i18n::text(language, Label::Quit),
+        true,
+        Some("CmdOrCtrl+Q"),
+    )?;
 
     // Build the menu
-    let menu = Menu::with_items(app, &[&show_i, &hide_i, &quit_i])?;
+    let menu = Menu::with_items(
+        app,
+        &[
+            &toggle_i,
+            &start_i,
+            &restart_i,
+            &pause_i,
+            &url_i,
+            &copy_url_i,
+            &docs_i,
+            &profiles_i,
+            &autostart_i,
+            &settings_i,
+            &open_logs_i,
+            &console_i,
+            &diagnostics_i,
+            &updates_i,
+            &about_i,
+            &relaunch_i,
+            &quit_i,
+        ],
+    )?;
+    app.manage(LabeledItems(vec![
+        (start_i.clone(), Label::StartBackend),
+        (restart_i, Label::RestartBackend),
+        (copy_url_i, Label::CopyBackendUrl),
+        (docs_i.clone(), Label::OpenApiDocs),
+        (settings_i, Label::Settings),
+        (open_logs_i, Label::OpenLogs),
+        (console_i, Label::ShowConsole),
+        (diagnostics_i, Label::ExportDiagnostics),
+        (updates_i, Label::CheckForUpdates),
+        (about_i, Label::About),
+        (relaunch_i, Label::Relaunch),
+        (quit_i, Label::Quit),
+    ]));
+    app.manage(ToggleItem(toggle_i));
+    app.manage(PauseItem(pause_i));
+    app.manage(StartItem(start_i));
+    app.manage(UrlItem(url_i));
+    app.manage(DocsItem(docs_i));
+    app.manage(AutostartItem(autostart_i));
+    app.manage(ProfileMenu(profiles_i));
 
-    // Get the tray icon
-    // Use the default icon from icons directory
-    let icon = app.default_window_icon().cloned().unwrap();
+    // Get the tray icon; elsewhere the app icon is used until the status icon is set
+    let icon = if cfg!(target_os = "macos") {
+        Image::from_bytes(TEMPLATE_ICON)?
+    } else {
+        app.default_window_icon().cloned().unwrap()
+    };
 
     // Create the system tray
-    let _tray = TrayIconBuilder::new()
+    let tray = TrayIconBuilder::with_id(TRAY_ID)
         .icon(icon)
+        .icon_as_template(cfg!(target_os = "macos"))
+        .tooltip(tooltip_text(app, &crate::backend::backend_status(app)))
         .menu(&menu)
-        .show_menu_on_left_click(false)
+        .show_menu_on_left_click(settings.tray_click == TrayClick::Menu)
         .on_menu_event(|app, event| match event.id.as_ref() {
-            "show" => {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
+            "toggle" => toggle_main_window(app),
+            // Only in the macOS dock menu, which has no toggle
+            "show" => show_main_window(app),
+            "hide" => hide_main_window(app),
+            // These wait on the backend process, so they get their own thread
+            // rather than blocking the event loop
+            "start" => {
+                let app = app.clone();
+                std::thread::spawn(move || start_backend(&app));
+            }
+            "restart" => {
+                let app = app.clone();
+                std::thread::spawn(move || {
+                    if let Err(e) = crate::backend::restart_backend_server(&app) {
+                        eprintln!("Failed to restart backend: {}", e);
+                    }
+                });
+            }
+            "pause" => {
+                let app = app.clone();
+                std::thread::spawn(move || {
+                    if !crate::backend::is_paused(&app) {
+                        crate::backend::pause_backend_server(&app, PauseMode::Preferred);
+                    } else if let Err(e) = crate::backend::resume_backend_server(&app) {
+                        eprintln!("Failed to resume backend: {}", e);
+                    }
+                });
+            }
+            "copy_url" => copy_backend_url(app),
+            "open_docs" => open_backend_docs(app),
+            "autostart" => {
+                let enabled = !crate::autostart::is_enabled(app);
+                if let Err(e) = crate::autostart::set_enabled(app, enabled) {
+                    eprintln!("{}", e);
+                }
+                // The item flips its own check mark, so put it back if that failed
+                set_autostart_checked(app, crate::autostart::is_enabled(app));
+            }
+            "settings" => {
+                if let Err(e) = crate::settings_window::open(app) {
+                    eprintln!("Failed to open settings window: {}", e);
+                }
+            }
+            "open_logs" => {
+                if let Err(e) = crate::backend_log::reveal(app) {
+                    eprintln!("{}", e);
                 }
             }
-            "hide" => {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.hide();
+            "console" => {
+                if let Err(e) = crate::console_window::open(app) {
+                    eprintln!("Failed to open backend console: {}", e);
                 }
             }
-            "quit" => {
-                // Stop backend before quitting
-                crate::backend::stop_backend_server();
-                std::process::exit(0);
+            "diagnostics" => {
+                // Asks the backend for its version and compresses the logs
+                let app = app.clone();
+                std::thread::spawn(move || crate::diagnostics::export(&app));
+            }
+            "check_updates" => crate::updater::check(app, true),
+            "about" => {
+                // Asking the backend for its version can block on the network
+                let app = app.clone();
+                std::thread::spawn(move || {
+                    crate::dialogs::show_about(&app, crate::backend::backend_version(&app))
+                });
+            }
+            "relaunch" => relaunch(app),
+            "quit" => request_quit(app),
+            id => {
+                if let Some(name) = id.strip_prefix(PROFILE_ID_PREFIX) {
+                    select_profile(app, name);
+                }
             }
-            _ => {}
         })
         .on_tray_icon_event(|tray, event| {
-            // Handle tray icon clicks
-            if let TrayIconEvent::Click {
-                button: MouseButton::Left,
-                button_state: MouseButtonState::Up,
-                ..
-            } = event
-            {
-                // Left click on tray icon - toggle window visibility
-                let app = tray.app_handle();
-                if let Some(window) = app.get_webview_window("main") {
-                    if window.is_visible().unwrap_or(false) {
-                        let _ = window.hide();
-                    } else {
-                        let _ = window.show();
-                        let _ = window.set_focus();
-                    }
+            // The menu may be about to open, so make sure it offers the right action
+            if matches!(
+                event,
+                TrayIconEvent::Enter { .. } | TrayIconEvent::Click { .. }
+            ) {
+                sync_toggle_label(tray.app_handle());
+            }
+
+            // Handle tray icon clicks, as configured. The setting is read
+            // each time so a change applies straight away.
+            match event {
+                TrayIconEvent::Click {
+                    button: MouseButton::Left,
+                    button_state: MouseButtonState::Up,
+                    ..
+                } => match settings::load(tray.app_handle()).tray_click {
+                    TrayClick::Toggle => toggle_main_window(tray.app_handle()),
+                    TrayClick::Show => show_main_window(tray.app_handle()),
+                    // The menu opens by itself
+                    TrayClick::Menu => {}
+                },
+                TrayIconEvent::DoubleClick {
+                    button: MouseButton::Left,
+                    ..
+                } if settings::load(tray.app_handle()).tray_click == TrayClick::Menu => {
+                    show_main_window(tray.app_handle())
                 }
+                _ => {}
             }
         })
         .build(app)?;
 
+    set_tray_status(&tray, &crate::backend::backend_status(app));
+    match app.try_state::<TrayHandle<R>>() {
+        Some(handle) => *handle.0.lock().unwrap() = Some(tray),
+        None => {
+            app.manage(TrayHandle(Mutex::new(Some(tray))));
+        }
+    }
+
     Ok(())
 }
+
+// The app's tray icon, if it has been created and not removed
+pub fn tray<R: Runtime>(app: &tauri::AppHandle<R>) -> Option<TrayIcon<R>> {
+    app.try_state::<TrayHandle<R>>()
+        .and_then(|handle| handle.0.lock().unwrap().clone())
+}
+
+// Update the tray icon and tooltip to match the backend status
+pub fn set_tray_status<R: Runtime>(tray: &TrayIcon<R>, status: &BackendStatus) {
+    let _ = tray.set_tooltip(Some(tooltip_text(tray.app_handle(), status)));
+
+    // Menu bar icons on macOS are monochrome template images, so there is no
+    // colored variant to switch to
+    if cfg!(target_os = "macos") {
+        return;
+    }
+
+    let variants = match status {
+        _ if unresponsive(status) => STARTING_ICON,
+        BackendStatus::Running { .. } | BackendStatus::External { .. } => RUNNING_ICON,
+        BackendStatus::Starting => {
+            start_spinner(tray);
+            return;
+        }
+        BackendStatus::Stopped
+        | BackendStatus::Paused
+        | BackendStatus::Suspended { .. }
+        | BackendStatus::Crashed => STOPPED_ICON,
+    };
+
+    stop_spinner();
+    set_icon(tray, variants);
+}
+
+fn set_icon<R: Runtime>(tray: &TrayIcon<R>, variants: [&[u8]; 2]) {
+    let bytes = variants[DARK_THEME.load(Ordering::SeqCst) as usize];
+
+    match Image::from_bytes(bytes) {
+        Ok(icon) => {
+            let _ = tray.set_icon(Some(icon));
+        }
+        Err(e) => eprintln!("Failed to load tray icon: {}", e),
+    }
+}
+
+// Cycle the spinner frames until the status moves on from Starting
+fn start_spinner<R: Runtime>(tray: &TrayIcon<R>) {
+    let id = {
+        let mut spinner = SPINNER.lock().unwrap();
+        if spinner.is_some() {
+            return;
+        }
+        let id = NEXT_SPINNER_ID.fetch_add(1, Ordering::SeqCst);
+        *spinner = Some(id);
+        id
+    };
+
+    let tray = tray.clone();
+    let current = move || *SPINNER.lock().unwrap() == Some(id);
+    std::thread::spawn(move || {
+        for frame in STARTING_FRAMES.iter().cycle() {
+            if !current() {
+                return;
+            }
+            // Setting the icon waits on the main thread, so no lock is held for it
+            set_icon(&tray, *frame);
+
+            // Stopped while drawing, so this frame may have replaced the new icon
+            if !current() {
+                let app = tray.app_handle();
+                update_tray_status(app, &crate::backend::backend_status(app));
+                return;
+            }
+            std::thread::sleep(SPINNER_INTERVAL);
+        }
+    });
+}
+
+fn stop_spinner() {
+    *SPINNER.lock().unwrap() = None;
+}
+
+// Let a left click open the menu or leave it to the click handler
+pub fn apply_tray_click<R: Runtime>(app: &tauri::AppHandle<R>, click: TrayClick) {
+    if let Some(tray) = tray(app) {
+        if let Err(e) = tray.set_show_menu_on_left_click(click == TrayClick::Menu) {
+            eprintln!("Failed to apply tray click setting: {}", e);
+        }
+    }
+}
+
+// Swap the icon variant when the system switches between light and dark.
+// macOS tints the template icon itself, so it needs nothing here.
+pub fn set_theme<R: Runtime>(app: &tauri::AppHandle<R>, theme: Theme) {
+    DARK_THEME.store(theme == Theme::Dark, Ordering::SeqCst);
+    update_tray_status(app, &crate::backend::backend_status(app));
+}
+
+// Running, but the last health check got no answer
+fn unresponsive(status: &BackendStatus) -> bool {
+    matches!(
+        status,
+        BackendStatus::Running { .. } | BackendStatus::External { .. }
+    ) && crate::health::current() == Some(crate::health::HealthState::Unhealthy)
+}
+
+fn tooltip_text<R: Runtime>(app: &tauri::AppHandle<R>, status: &BackendStatus) -> String {
+    match status {
+        _ if unresponsive(status) => "RenAI — backend not responding".to_string(),
+        BackendStatus::Running { pid, port } => {
+            let uptime = crate::backend::backend_uptime(app)
+                .map(|uptime| format!(", up {}", format_uptime(uptime)))
+                .unwrap_or_default();
+            let text = format!("RenAI — backend running (PID {}, :{}{})", pid, port, uptime);
+            // A sample from the previous process would be misleading
+            match crate::metrics::current().filter(|metrics| metrics.pid == *pid) {
+                Some(metrics) => format!("{} — {}", text, metrics.summary()),
+                None => text,
+            }
+        }
+        BackendStatus::External { port } => {
+            format!("RenAI — using external backend (:{})", port)
+        }
+        BackendStatus::Starting => "RenAI — backend starting".to_string(),
+        BackendStatus::Stopped => "RenAI — backend stopped".to_string(),
+        BackendStatus::Paused => "RenAI — backend paused".to_string(),
+        BackendStatus::Suspended { pid, .. } => {
+            format!("RenAI — backend suspended (PID {})", pid)
+        }
+        BackendStatus::Crashed => "RenAI — backend crashed".to_string(),
+    }
+}
+
+// Coarse enough for a tooltip that is only refreshed every few seconds
+fn format_uptime(uptime: Duration) -> String {
+    let mins = uptime.as_secs() / 60;
+    match (mins / 60, mins % 60) {
+        (0, 0) => "<1m".to_string(),
+        (0, mins) => format!("{}m", mins),
+        (hours, mins) => format!("{}h {}m", hours, mins),
+    }
+}
+
+pub fn update_tray_status<R: Runtime>(app: &tauri::AppHandle<R>, status: &BackendStatus) {
+    if let Some(tray) = tray(app) {
+        set_tray_status(&tray, status);
+    }
+    if let Some(item) = app.try_state::<PauseItem<R>>() {
+        let _ = item.0.set_text(pause_label(app));
+    }
+    if let Some(item) = app.try_state::<StartItem<R>>() {
+        let _ = item.0.set_enabled(can_start(status));
+    }
+    if let Some(item) = app.try_state::<UrlItem<R>>() {
+        let _ = item.0.set_text(url_label(app, status));
+    }
+    if let Some(item) = app.try_state::<DocsItem<R>>() {
+        let _ = item.0.set_enabled(serving(status));
+    }
+}
+
+// Whether there is a backend to send the browser to
+fn serving(status: &BackendStatus) -> bool {
+    matches!(
+        status,
+        BackendStatus::Running { .. } | BackendStatus::External { .. }
+    )
+}
+
+// The URL Copy Backend URL copies, after a dot that is filled while the
+// backend serves, half filled while it comes up and empty while there is none
+fn url_label<R: Runtime>(app: &tauri::AppHandle<R>, status: &BackendStatus) -> String {
+    let url = crate::backend::active_config(app).client_url();
+    let bullet = match status {
+        _ if unresponsive(status) => "◐",
+        BackendStatus::Running { .. } | BackendStatus::External { .. } => "●",
+        BackendStatus::Starting => "◐",
+        BackendStatus::Stopped
+        | BackendStatus::Paused
+        | BackendStatus::Suspended { .. }
+        | BackendStatus::Crashed => "○",
+    };
+    format!("{} {}", bullet, url)
+}
+
+// A paused backend is brought back with Resume instead
+fn can_start(status: &BackendStatus) -> bool {
+    matches!(status, BackendStatus::Stopped | BackendStatus::Crashed)
+}
+
+// Retry a backend that failed to start or was never started
+fn start_backend<R: Runtime>(app: &tauri::AppHandle<R>) {
+    let Err(e) = crate::backend::start_backend_with_retries(app) else {
+        return;
+    };
+    eprintln!("Failed to start backend: {}", e);
+
+    match e.root_cause() {
+        BackendError::PythonNotFound => crate::dialogs::show_python_missing(app),
+        BackendError::PythonTooOld { found, required } => {
+            crate::dialogs::show_python_too_old(app, found, required)
+        }
+        _ => {}
+    }
+}
+
+// Only the tooltip, for the regular resource usage updates
+pub fn refresh_tooltip<R: Runtime>(app: &tauri::AppHandle<R>) {
+    if let Some(tray) = tray(app) {
+        let status = crate::backend::backend_status(app);
+        let _ = tray.set_tooltip(Some(tooltip_text(app, &status)));
+    }
+}
+
+fn pause_label<R: Runtime>(app: &tauri::AppHandle<R>) -> &'static str {
+    let label = if crate::backend::is_paused(app) {
+        Label::ResumeBackend
+    } else {
+        Label::PauseBackend
+    };
+    i18n::text(i18n::language(app), label)
+}
+
+#[tauri::command]
+pub fn set_task_in_progress(in_progress: bool) {
+    TASK_IN_PROGRESS.store(in_progress, Ordering::SeqCst);
+}
+
+// One checkable entry per profile, with the active one checked
+fn fill_profile_menu<R: Runtime>(
+    app: &tauri::AppHandle<R>,
+    submenu: &Submenu<R>,
+    settings: &Settings,
+) -> tauri::Result<()> {
+    for item in submenu.items()? {
+        submenu.remove(&item)?;
+    }
+
+    let active = settings.active_profile.as_deref().unwrap_or_default();
+    let profiles = settings
+        .profiles
+        .iter()
+        .map(|p| (p.name.as_str(), p.name.as_str()));
+    let default = i18n::text(i18n::language(app), Label::DefaultProfile);
+    let entries = std::iter::once(("", default)).chain(profiles);
+
+    for (name, label) in entries {
+        let id = format!("{}{}", PROFILE_ID_PREFIX, name);
+        let item = CheckMenuItem::with_id(app, id, label, true, name == active, None::<&str>)?;
+        submenu.append(&item)?;
+    }
+
+    Ok(())
+}
+
+pub fn refresh_profile_menu<R: Runtime>(app: &tauri::AppHandle<R>, settings: &Settings) {
+    if let Some(menu) = app.try_state::<ProfileMenu<R>>() {
+        if let Err(e) = fill_profile_menu(app, &menu.0, settings) {
+            eprintln!("Failed to update the profile menu: {}", e);
+        }
+    }
+}
+
+// Make `name` the active profile and restart the backend under it
+fn select_profile<R: Runtime>(app: &tauri::AppHandle<R>, name: &str) {
+    let mut settings = settings::load(app);
+    settings.active_profile = (!name.is_empty()).then(|| name.to_string());
+    let saved = settings::save(app, &settings);
+    if let Err(e) = &saved {
+        eprintln!("Failed to switch backend profile: {}", e);
+    }
+    // Re-check the right entry, since clicking toggles the one clicked; after
+    // a failed save that is still the old profile
+    refresh_profile_menu(app, &settings::load(app));
+    if saved.is_err() {
+        return;
+    }
+
+    println!("Switching to backend profile {:?}", name);
+    let app = app.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = crate::backend::restart_backend_server(&app) {
+            eprintln!("Failed to restart backend: {}", e);
+        }
+    });
+}
+
+// Put the running backend's address on the clipboard, e.g. for opening its API docs
+fn copy_backend_url<R: Runtime>(app: &tauri::AppHandle<R>) {
+    let config = crate::backend::active_config(app);
+    let url = config.client_url();
+
+    if let Err(e) = app.clipboard().write_text(url.clone()) {
+        eprintln!("Failed to copy backend URL: {}", e);
+        return;
+    }
+
+    let _ = app
+        .notification()
+        .builder()
+        .title("RenAI")
+        .body(i18n::text(i18n::language(app), Label::CopiedUrl).replace("{url}", &url))
+        .show();
+}
+
+// Open FastAPI's Swagger UI, on the port and scheme the backend actually serves
+fn open_backend_docs<R: Runtime>(app: &tauri::AppHandle<R>) {
+    let url = format!("{}/docs", crate::backend::active_config(app).client_url());
+    if let Err(e) = app.opener().open_url(&url, None::<&str>) {
+        eprintln!("Failed to open {}: {}", url, e);
+    }
+}
+
+// Quit right away, or after confirmation if a task is running and the user wants
+// to be asked
+pub fn request_quit<R: Runtime>(app: &tauri::AppHandle<R>) {
+    let busy = TASK_IN_PROGRESS.load(Ordering::SeqCst);
+    if busy && crate::settings::load(app).confirm_quit {
+        let handle = app.clone();
+        crate::dialogs::confirm_quit(app, move || quit(&handle));
+    } else {
+        quit(app);
+    }
+}
+
+fn quit<R: Runtime>(app: &tauri::AppHandle<R>) {
+    if let Some(window) = app.get_webview_window("main") {
+        crate::window_state::save(&window);
+    }
+    crate::window_state::shutting_down();
+    remove_tray(app);
+    // The backend is stopped by the RunEvent::Exit handler
+    app.exit(0);
+}
+
+// Take the icon out of the system tray now. On Windows it otherwise lingers
+// after exit until the pointer passes over it.
+pub fn remove_tray<R: Runtime>(app: &tauri::AppHandle<R>) {
+    // Its thread holds a handle of its own, which would keep the icon alive
+    stop_spinner();
+
+    if let Some(handle) = app.try_state::<TrayHandle<R>>() {
+        handle.0.lock().unwrap().take();
+    }
+    if let Some(tray) = app.remove_tray_by_id(TRAY_ID) {
+        let _ = tray.set_visible(false);
+    }
+}
+
+// Restart the whole app, e.g. to apply settings that are only read at launch
+pub fn relaunch<R: Runtime>(app: &tauri::AppHandle<R>) {
+    let app = app.clone();
+    // Called from the tray and from the frontend, both on the event loop
+    std::thread::spawn(move || {
+        if let Some(window) = app.get_webview_window("main") {
+            crate::window_state::save(&window);
+        }
+        crate::window_state::shutting_down();
+
+        // Only returns once the backend is gone, so the new instance can take its port
+        crate::backend::save_backend_state(&app);
+        crate::backend::stop_backend_server(&app);
+        app.restart();
+    });
+}
+
+#[tauri::command]
+pub fn relaunch_app<R: Runtime>(app: tauri::AppHandle<R>) {
+    relaunch(&app);
+}
+
+pub fn show_main_window<R: Runtime>(app: &tauri::AppHandle<R>) {
+    if let Some(window) = app.get_webview_window("main") {
+        // The window starts hidden so the saved geometry can be applied first
+        crate::window_state::restore_once(&window);
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+        crate::window_state::save_visibility(&window, true);
+    }
+    sync_toggle_label(app);
+    crate::idle::window_shown(app);
+}
+
+pub fn hide_main_window<R: Runtime>(app: &tauri::AppHandle<R>) {
+    if let Some(window) = app.get_webview_window("main") {
+        // Only a visible window's geometry is saved
+        crate::window_state::save(&window);
+        let _ = window.hide();
+        crate::window_state::save_visibility(&window, false);
+    }
+    sync_toggle_label(app);
+    crate::idle::window_hidden();
+}
+
+// Hide the window from its close button, and the first time explain that RenAI
+// is still running, so the tray icon staying behind isn't a surprise
+pub fn close_to_tray<R: Runtime>(app: &tauri::AppHandle<R>) {
+    hide_main_window(app);
+
+    if CLOSED_TO_TRAY_NOTICE.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let language = i18n::language(app);
+    let body = i18n::text(language, Label::StillRunningBody)
+        .replace("{quit}", i18n::text(language, Label::Quit));
+    let _ = app
+        .notification()
+        .builder()
+        .title(i18n::text(language, Label::StillRunning))
+        .body(body)
+        .show();
+}
+
+pub fn toggle_main_window<R: Runtime>(app: &tauri::AppHandle<R>) {
+    if main_window_visible(app) {
+        hide_main_window(app);
+    } else {
+        show_main_window(app);
+    }
+}
+
+pub fn main_window_visible<R: Runtime>(app: &tauri::AppHandle<R>) -> bool {
+    app.get_webview_window("main")
+        .and_then(|window| window.is_visible().ok())
+        .unwrap_or(false)
+}
+
+fn toggle_label<R: Runtime>(app: &tauri::AppHandle<R>) -> &'static str {
+    let label = if main_window_visible(app) {
+        Label::Hide
+    } else {
+        Label::ShowApp
+    };
+    i18n::text(i18n::language(app), label)
+}
+
+pub fn set_autostart_checked<R: Runtime>(app: &tauri::AppHandle<R>, checked: bool) {
+    if let Some(item) = app.try_state::<AutostartItem<R>>() {
+        let _ = item.0.set_checked(checked);
+    }
+}
+
+// Put the whole menu in the current language, e.g. after the locale setting changed
+pub fn relabel_menu<R: Runtime>(app: &tauri::AppHandle<R>, settings: &Settings) {
+    let language = i18n::language(app);
+
+    if let Some(items) = app.try_state::<LabeledItems<R>>() {
+        for (item, label) in &items.0 {
+            let _ = item.set_text(i18n::text(language, *label));
+        }
+    }
+    if let Some(item) = app.try_state::<AutostartItem<R>>() {
+        let _ = item.0.set_text(i18n::text(language, Label::StartOnLogin));
+    }
+    if let Some(menu) = app.try_state::<ProfileMenu<R>>() {
+        let _ = menu.0.set_text(i18n::text(language, Label::BackendProfile));
+    }
+    refresh_profile_menu(app, settings);
+    sync_toggle_label(app);
+    if let Some(item) = app.try_state::<PauseItem<R>>() {
+        let _ = item.0.set_text(pause_label(app));
+    }
+    #[cfg(target_os = "macos")]
+    if let Err(e) = app.run_on_main_thread(move || crate::dock::relabel(language)) {
+        eprintln!("Failed to relabel the dock menu: {}", e);
+    }
+}
+
+// Relabel the show/hide item after the window was shown or hidden
+pub fn sync_toggle_label<R: Runtime>(app: &tauri::AppHandle<R>) {
+    if let Some(item) = app.try_state::<ToggleItem<R>>() {
+        let _ = item.0.set_text(toggle_label(app));
+    }
+}
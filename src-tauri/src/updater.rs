@@ -0,0 +1,91 @@
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+use crate::{backend, dialogs};
+
+// Look for a newer release and offer to install it. Checks the user asked for
+// also report when there is nothing new or the check failed; background ones stay quiet.
+pub fn check<R: Runtime>(app: &AppHandle<R>, interactive: bool) {
+    if !has_signing_key(app) {
+        println!("Updates are disabled: no updater public key is configured");
+        if interactive {
+            dialogs::show_update_failed(
+                app,
+                "This build can't verify updates. Download new releases from the website.",
+            );
+        }
+        return;
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match find_update(&app).await {
+            Ok(Some(update)) => {
+                let (version, notes) = (update.version.clone(), update.body.clone());
+                let handle = app.clone();
+                dialogs::confirm_update(&app, &version, notes.as_deref(), move || {
+                    tauri::async_runtime::spawn(install(handle, update));
+                });
+            }
+            Ok(None) => {
+                if interactive {
+                    dialogs::show_up_to_date(&app);
+                }
+            }
+            Err(e) => {
+                eprintln!("Warning: Update check failed: {}", e);
+                if interactive {
+                    dialogs::show_update_failed(&app, &e.to_string());
+                }
+            }
+        }
+    });
+}
+
+// Every update is verified against plugins.updater.pubkey before installing,
+// so without one a download could only ever fail
+fn has_signing_key<R: Runtime>(app: &AppHandle<R>) -> bool {
+    app.config()
+        .plugins
+        .0
+        .get("updater")
+        .and_then(|updater| updater.get("pubkey"))
+        .and_then(|key| key.as_str())
+        .is_some_and(|key| !key.trim().is_empty())
+}
+
+async fn find_update<R: Runtime>(
+    app: &AppHandle<R>,
+) -> tauri_plugin_updater::Result<Option<Update>> {
+    let handle = app.clone();
+    app.updater_builder()
+        // The Windows installer exits the app itself, which skips RunEvent::Exit,
+        // so the backend has to be stopped here or it outlives the old version
+        .on_before_exit(move || {
+            crate::window_state::shutting_down();
+            backend::stop_backend_server(&handle);
+            handle.cleanup_before_exit();
+        })
+        .build()?
+        .check()
+        .await
+}
+
+async fn install<R: Runtime>(app: AppHandle<R>, update: Update) {
+    if let Err(e) = update.download_and_install(|_, _| {}, || {}).await {
+        eprintln!("Failed to install update {}: {}", update.version, e);
+        dialogs::show_update_failed(&app, &e.to_string());
+        return;
+    }
+    println!("✓ Installed update {}", update.version);
+
+    // Stop the backend before relaunching so the new instance can take its port
+    crate::window_state::shutting_down();
+    let handle = app.clone();
+    if let Err(e) =
+        tauri::async_runtime::spawn_blocking(move || backend::stop_backend_server(&handle)).await
+    {
+        eprintln!("Warning: Failed to stop backend before relaunching: {}", e);
+    }
+    app.restart();
+}
@@ -0,0 +1,67 @@
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Runtime};
+
+use crate::backend::{self, BackendStatus};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+// A tick that took this much longer than CHECK_INTERVAL by the wall clock
+// means the machine was asleep, since the thread can't run while suspended
+const SLEEP_THRESHOLD: Duration = Duration::from_secs(30);
+
+// Lets the watchdog deal with a backend that died in the meantime, and the
+// network come back, before the backend is checked
+const SETTLE_DELAY: Duration = Duration::from_secs(5);
+
+// Notice when the system resumes from sleep and make sure the backend made it
+// through: one that died or stopped answering while suspended is restarted
+pub fn spawn_monitor<R: Runtime>(app_handle: AppHandle<R>) {
+    std::thread::spawn(move || {
+        let mut last_tick = SystemTime::now();
+        let mut was_running = false;
+
+        loop {
+            std::thread::sleep(CHECK_INTERVAL);
+
+            let now = SystemTime::now();
+            // The clock being set back isn't a wake either
+            let elapsed = now.duration_since(last_tick).unwrap_or_default();
+            last_tick = now;
+
+            if elapsed >= CHECK_INTERVAL + SLEEP_THRESHOLD && was_running {
+                println!(
+                    "System resumed after {}s, checking the backend",
+                    elapsed.as_secs()
+                );
+                std::thread::sleep(SETTLE_DELAY);
+                check_backend(&app_handle);
+                last_tick = SystemTime::now();
+            }
+
+            was_running = matches!(
+                backend::backend_status(&app_handle),
+                BackendStatus::Running { .. } | BackendStatus::Starting
+            );
+        }
+    });
+}
+
+fn check_backend<R: Runtime>(app_handle: &AppHandle<R>) {
+    match backend::backend_status(app_handle) {
+        BackendStatus::Running { .. } => {
+            if backend::probe_health(&backend::active_config(app_handle)) {
+                println!("✓ Backend survived the sleep");
+                return;
+            }
+        }
+        // Died, and the watchdog couldn't bring it back
+        BackendStatus::Crashed => {}
+        // The watchdog is restarting it, or it was stopped on purpose
+        _ => return,
+    }
+
+    eprintln!("Warning: Backend didn't survive the sleep, restarting it");
+    if let Err(e) = backend::restart_backend_server(app_handle) {
+        eprintln!("Failed to restart backend after wake: {}", e);
+    }
+}
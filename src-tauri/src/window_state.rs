@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize, Runtime, WebviewWindow};
+
+const STATE_FILE: &str = "window-state.json";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct WindowState {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+    // Whether the window was showing rather than tucked in the tray; files
+    // saved before this was recorded count as showing
+    #[serde(default = "showing")]
+    pub visible: bool,
+}
+
+fn showing() -> bool {
+    true
+}
+
+// Set once the app starts shutting down, after which the window may be hidden
+// on the way out; the next launch should see how it was before that
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+fn state_path<R: Runtime>(app_handle: &AppHandle<R>) -> Option<PathBuf> {
+    app_handle
+        .path()
+        .app_config_dir()
+        .ok()
+        .map(|dir| dir.join(STATE_FILE))
+}
+
+// Saved geometry, or None if nothing was saved yet or the file is unreadable
+pub fn load<R: Runtime>(app_handle: &AppHandle<R>) -> Option<WindowState> {
+    let contents = std::fs::read_to_string(state_path(app_handle)?).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+// Save the window's current geometry. Hidden or minimized windows are skipped so
+// hiding to the tray never overwrites the last visible geometry, and while
+// maximized only the flag changes so the normal size survives.
+pub fn save<R: Runtime>(window: &WebviewWindow<R>) {
+    if !window.is_visible().unwrap_or(false) || window.is_minimized().unwrap_or(false) {
+        return;
+    }
+
+    let maximized = window.is_maximized().unwrap_or(false);
+    let previous = load(window.app_handle());
+
+    let mut state = match previous {
+        Some(previous) if maximized => previous,
+        _ => {
+            let (Ok(position), Ok(size)) = (window.outer_position(), window.inner_size()) else {
+                return;
+            };
+            WindowState {
+                x: position.x,
+                y: position.y,
+                width: size.width,
+                height: size.height,
+                maximized,
+                visible: true,
+            }
+        }
+    };
+    state.maximized = maximized;
+    state.visible = true;
+
+    if let Err(e) = write(window.app_handle(), &state) {
+        eprintln!("Failed to save window state: {}", e);
+    }
+}
+
+// Record whether the window is showing or in the tray, so the next launch
+// can bring it back the same way
+pub fn save_visibility<R: Runtime>(window: &WebviewWindow<R>, visible: bool) {
+    if SHUTTING_DOWN.load(Ordering::SeqCst) {
+        return;
+    }
+
+    let mut state = match load(window.app_handle()) {
+        Some(state) if state.visible == visible => return,
+        Some(state) => state,
+        // Nothing saved yet, so keep the window's current geometry with it
+        None => {
+            let (Ok(position), Ok(size)) = (window.outer_position(), window.inner_size()) else {
+                return;
+            };
+            WindowState {
+                x: position.x,
+                y: position.y,
+                width: size.width,
+                height: size.height,
+                maximized: false,
+                visible,
+            }
+        }
+    };
+    state.visible = visible;
+
+    if let Err(e) = write(window.app_handle(), &state) {
+        eprintln!("Failed to save window visibility: {}", e);
+    }
+}
+
+// Whether the window was showing when the app last ran, which it is taken to
+// be if nothing was saved
+pub fn was_visible<R: Runtime>(app_handle: &AppHandle<R>) -> bool {
+    load(app_handle).map_or(true, |state| state.visible)
+}
+
+// Stop recording visibility for the rest of this run
+pub fn shutting_down() {
+    SHUTTING_DOWN.store(true, Ordering::SeqCst);
+}
+
+fn write<R: Runtime>(app_handle: &AppHandle<R>, state: &WindowState) -> Result<(), String> {
+    let path = state_path(app_handle).ok_or("No app config directory")?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+
+    let contents = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+    std::fs::write(&path, contents).map_err(|e| e.to_string())
+}
+
+// Set once the saved geometry has been applied, which happens the first time
+// the window is shown - that may be long after startup when starting hidden
+static RESTORED: AtomicBool = AtomicBool::new(false);
+
+pub fn restore_once<R: Runtime>(window: &WebviewWindow<R>) {
+    if !RESTORED.swap(true, Ordering::SeqCst) {
+        restore(window);
+    }
+}
+
+// Apply the saved geometry, if any, before the window is shown
+fn restore<R: Runtime>(window: &WebviewWindow<R>) {
+    let Some(state) = load(window.app_handle()) else {
+        return;
+    };
+    let state = clamp_to_screen(window, state);
+
+    let _ = window.set_size(PhysicalSize::new(state.width, state.height));
+    let _ = window.set_position(PhysicalPosition::new(state.x, state.y));
+    if state.maximized {
+        let _ = window.maximize();
+    }
+}
+
+// If the saved rect no longer overlaps any monitor (e.g. the monitor it was on
+// got disconnected), pull it into the primary monitor's work area
+fn clamp_to_screen<R: Runtime>(window: &WebviewWindow<R>, mut state: WindowState) -> WindowState {
+    let monitors = window.available_monitors().unwrap_or_default();
+    let on_screen = monitors.iter().any(|monitor| {
+        let area = monitor.work_area();
+        state.x < area.position.x + area.size.width as i32
+            && state.x + state.width as i32 > area.position.x
+            && state.y < area.position.y + area.size.height as i32
+            && state.y + state.height as i32 > area.position.y
+    });
+    if on_screen {
+        return state;
+    }
+
+    let Ok(Some(primary)) = window.primary_monitor() else {
+        return state;
+    };
+    let area = primary.work_area();
+
+    state.width = state.width.min(area.size.width);
+    state.height = state.height.min(area.size.height);
+    state.x = state.x.clamp(
+        area.position.x,
+        area.position.x + (area.size.width - state.width) as i32,
+    );
+    state.y = state.y.clamp(
+        area.position.y,
+        area.position.y + (area.size.height - state.height) as i32,
+    );
+
+    state
+}